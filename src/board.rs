@@ -1,13 +1,103 @@
+use crate::boundary::Boundary;
 use crate::cell_state::CellState;
+use crate::cell_transition::CellTransition;
+use crate::colors::Colors;
 use crate::coord::Coord;
+use crate::font::Font;
+use crate::game::Game;
+use crate::neighbourhood::Neighbourhood;
+use crate::parse_error::ParseError;
+use crate::patterns;
+use crate::rle::{self, RleError};
+use crate::rng::Rng;
+use crate::rules::Rules;
+use crate::share_code::{self, DecodeError};
+use crate::stamp_mode::StampMode;
+use crate::symmetry_kind::SymmetryKind;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+#[cfg(feature = "png")]
+use std::io;
+use std::ops::{Index, IndexMut};
 
-/// A rectangular board for a life-like game
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// A rectangular board for a life-like game. With the `serde` feature, it is serialized as its
+/// rows of cells together with its boundary and neighbourhood
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "RawBoard", try_from = "RawBoard")
+)]
 pub struct Board {
     /// The cells on the board
     cells: Vec<Vec<CellState>>,
     /// Coordinates of all cells on the board
     cell_coords: Vec<Coord>,
+    /// How the edges of the board behave
+    boundary: Boundary,
+    /// Which cells around a cell count as its neighbours
+    neighbourhood: Neighbourhood,
+    /// Number of live cells, kept up to date as cells change. It is `None` after cells have been
+    /// changed through [`IndexMut`], and is counted again when it is next needed
+    live_count: Option<usize>,
+}
+
+impl PartialEq for Board {
+    fn eq(&self, other: &Board) -> bool {
+        // The live count follows from the cells, and may only be missing on one of the boards
+        self.cells == other.cells
+            && self.boundary == other.boundary
+            && self.neighbourhood == other.neighbourhood
+    }
+}
+
+impl Eq for Board {}
+
+/// A board as it is serialized, before the rows are checked to make up a rectangle
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct RawBoard {
+    cells: Vec<Vec<CellState>>,
+    boundary: Boundary,
+    neighbourhood: Neighbourhood,
+}
+
+#[cfg(feature = "serde")]
+impl From<Board> for RawBoard {
+    fn from(board: Board) -> RawBoard {
+        RawBoard {
+            cells: board.cells,
+            boundary: board.boundary,
+            neighbourhood: board.neighbourhood,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RawBoard> for Board {
+    type Error = String;
+
+    fn try_from(raw: RawBoard) -> Result<Board, String> {
+        let width = raw.cells.first().map_or(0, Vec::len);
+        if width == 0 {
+            return Err("Board has no cells".to_string());
+        }
+
+        if let Some(y) = raw.cells.iter().position(|row| row.len() != width) {
+            return Err(format!(
+                "Row {} has {} cells, expected {}",
+                y,
+                raw.cells[y].len(),
+                width
+            ));
+        }
+
+        let mut board = Board::from_grid(&raw.cells);
+        board.set_boundary(raw.boundary);
+        board.set_neighbourhood(raw.neighbourhood);
+
+        Ok(board)
+    }
 }
 
 impl Board {
@@ -49,6 +139,9 @@ impl Board {
         Board {
             cell_coords,
             cells: rows,
+            boundary: Boundary::Toroidal,
+            neighbourhood: Neighbourhood::Moore,
+            live_count: Some(0),
         }
     }
 
@@ -79,6 +172,8 @@ impl Board {
     ///
     /// # Panics
     /// If there are no rows or no columns in the grid
+    // Taking a slice would be more general, but would change the public signature
+    #[allow(clippy::ptr_arg)]
     pub fn from_grid(grid: &Vec<Vec<CellState>>) -> Board {
         // Figure out the board's dimensions
         let height = grid.len();
@@ -99,6 +194,75 @@ impl Board {
         board
     }
 
+    /// Creates a new board by calling a closure with the x and y coordinates of each cell to get
+    /// its state
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, CellState};
+    ///
+    /// let board = Board::from_fn(4, 2, |x, y| {
+    ///     if (x + y) % 2 == 0 {
+    ///         CellState::Alive
+    ///     } else {
+    ///         CellState::Dead
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(board.to_str('#', '_'), vec![
+    ///     "#_#_",
+    ///     "_#_#",
+    /// ].join("\n"));
+    /// ```
+    ///
+    /// # Panics
+    /// If width or height is 0
+    pub fn from_fn<F: Fn(usize, usize) -> CellState>(width: usize, height: usize, f: F) -> Board {
+        let mut board = Board::new(width, height);
+
+        for y in 0..height {
+            for x in 0..width {
+                board.set_cell_state(&Coord::new(x, y), f(x, y));
+            }
+        }
+
+        board
+    }
+
+    /// Creates a new board filled with copies of a pattern, repeated `repeats_x` times across and
+    /// `repeats_y` times down
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::Board;
+    ///
+    /// let pattern = Board::from_str(&vec![
+    ///     "#_",
+    ///     "__",
+    /// ].join("\n"), '#');
+    ///
+    /// let board = Board::tiled(&pattern, 3, 2);
+    ///
+    /// assert_eq!(board.to_str('#', '_'), vec![
+    ///     "#_#_#_",
+    ///     "______",
+    ///     "#_#_#_",
+    ///     "______",
+    /// ].join("\n"));
+    /// ```
+    ///
+    /// # Panics
+    /// If either number of repeats is 0
+    pub fn tiled(pattern: &Board, repeats_x: usize, repeats_y: usize) -> Board {
+        let (width, height) = (pattern.width(), pattern.height());
+
+        Board::from_fn(width * repeats_x, height * repeats_y, |x, y| {
+            pattern
+                .get_cell_state(&Coord::new(x % width, y % height))
+                .clone()
+        })
+    }
+
     /// Creates a new grid from a string. Newlines separate the rows, and all other characters
     /// except the alive character count as dead. Lines will be padded with dead cells to have
     /// the same length as the longest line, and a trailing newline counts as a dead row!
@@ -164,551 +328,4581 @@ impl Board {
         Board::from_grid(&grid)
     }
 
-    /// Creates a string representation of the board
+    /// Creates a new board from a string like [`Board::from_str`], but skips all lines starting
+    /// with the comment prefix
     ///
     /// # Examples
     /// ```
-    /// use game_of_life::Board;
+    /// use game_of_life::{Board, Coord, CellState};
     ///
     /// let board_str = vec![
-    ///     "#___#",
-    ///     "_#_#_",
-    ///     "__#__",
-    ///     "_#_#_",
-    ///     "#___#",
+    ///    "// A diagonal",
+    ///    "// drawn by hand",
+    ///    "#_",
+    ///    "_#",
     /// ].join("\n");
     ///
-    /// let board = Board::from_str(&board_str, '#');
+    /// let board = Board::from_str_with_comments(&board_str, '#', "//");
     ///
-    /// assert_eq!(board.to_str('#', '_'), board_str);
+    /// assert_eq!(board.width(), 2);
+    /// assert_eq!(board.height(), 2);
+    /// assert_eq!(board.get_cell_state(&Coord::new(0, 0)), &CellState::Alive);
+    /// assert_eq!(board.get_cell_state(&Coord::new(1, 1)), &CellState::Alive);
     /// ```
-    pub fn to_str(&self, alive: char, dead: char) -> String {
-        if alive == '\n' || alive == '\r' {
-            panic!("The alive character cannot be a newline character");
+    ///
+    /// # Panics
+    /// If the alive character is a newline character, if the comment prefix is empty or starts
+    /// with the alive character, as it would then match rows of cells too, or if there are no rows
+    /// or no columns left after removing the comments
+    pub fn from_str_with_comments(string: &str, alive: char, comment_prefix: &str) -> Board {
+        if comment_prefix.is_empty() {
+            panic!("Comment prefix must not be empty");
         }
-        if dead == '\n' || dead == '\r' {
-            panic!("The alive character cannot be a newline character");
+        if comment_prefix.starts_with(alive) {
+            panic!(
+                "Comment prefix must not start with the alive character '{}'",
+                alive
+            );
         }
 
-        self.cells
-            .iter()
-            .map(|row| {
-                row.iter()
-                    .map(|cell_state| match cell_state {
-                        CellState::Alive => alive,
-                        CellState::Dead => dead,
-                    })
-                    .collect::<String>()
-            })
-            .collect::<Vec<String>>()
-            .join("\n")
+        let uncommented = string
+            .replace('\r', "")
+            .split('\n')
+            .filter(|line| !line.starts_with(comment_prefix))
+            .collect::<Vec<&str>>()
+            .join("\n");
+
+        Board::from_str(&uncommented, alive)
     }
 
-    /// The width of the board
+    /// Creates a new board from a string like [`Board::from_str`], but first removes the
+    /// indentation shared by all lines, as well as blank lines at the start and end. This lets
+    /// patterns be written as indented string literals in code. Whitespace counts as indentation
+    /// unless it is the alive character
     ///
     /// # Examples
     /// ```
     /// use game_of_life::Board;
     ///
-    /// let width = 15;
-    /// let height = 10;
-    /// let board = Board::new(width, height);
+    /// let board = Board::from_str_aligned("
+    ///     .O.
+    ///     ..O
+    ///     OOO
+    /// ", 'O');
     ///
-    /// assert_eq!(board.width(), width);
+    /// assert_eq!(board.to_str('#', '_'), vec![
+    ///     "_#_",
+    ///     "__#",
+    ///     "###",
+    /// ].join("\n"));
     /// ```
-    pub fn width(&self) -> usize {
-        self.cells[0].len()
+    ///
+    /// # Panics
+    /// If the alive character is a newline character, or if there are no rows or no columns left
+    /// after removing the indentation
+    pub fn from_str_aligned(string: &str, alive: char) -> Board {
+        let is_indent = |c: char| c.is_whitespace() && c != alive;
+        let is_blank = |line: &&str| line.chars().all(is_indent);
+
+        let string = string.replace('\r', "");
+        let mut lines: Vec<&str> = string.split('\n').skip_while(is_blank).collect();
+        while lines.last().is_some_and(is_blank) {
+            lines.pop();
+        }
+
+        let indent = lines
+            .iter()
+            .filter(|line| !is_blank(line))
+            .map(|line| line.chars().take_while(|&c| is_indent(c)).count())
+            .min()
+            .unwrap_or(0);
+
+        let aligned = lines
+            .iter()
+            .map(|line| line.chars().skip(indent).collect::<String>())
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        Board::from_str(&aligned, alive)
     }
 
-    /// The height of the board
+    /// Creates a new board from the plaintext `.cells` format, where `O` is a live cell and `.`
+    /// is a dead cell. Lines starting with `!` are comments and are skipped, and rows may leave
+    /// out dead cells at the end. Newlines at the end of the file are ignored
     ///
     /// # Examples
     /// ```
     /// use game_of_life::Board;
     ///
-    /// let width = 15;
-    /// let height = 10;
-    /// let board = Board::new(width, height);
+    /// let cells = vec![
+    ///     "!Name: Glider",
+    ///     "!",
+    ///     ".O",
+    ///     "..O",
+    ///     "OOO",
+    ///     "",
+    /// ].join("\n");
     ///
-    /// assert_eq!(board.height(), height);
+    /// let board = Board::from_cells(&cells);
+    ///
+    /// assert_eq!(board.to_str('#', '_'), vec![
+    ///     "_#_",
+    ///     "__#",
+    ///     "###",
+    /// ].join("\n"));
     /// ```
-    pub fn height(&self) -> usize {
-        self.cells.len()
+    ///
+    /// # Panics
+    /// If there are no rows or no columns left after removing the comments
+    pub fn from_cells(string: &str) -> Board {
+        Board::from_str_with_comments(string.trim_end_matches(['\n', '\r']), 'O', "!")
     }
 
-    /// Set of all cell coordinates on the board
+    /// Creates a new board from the Life 1.06 format: a `#Life 1.06` header line followed by the
+    /// `x y` coordinates of each live cell, one per line. Coordinates may be negative, and the
+    /// board is the size of the bounding box of the live cells. Other lines starting with `#` and
+    /// blank lines are skipped
     ///
     /// # Examples
     /// ```
-    /// use game_of_life::{Board, Coord};
+    /// use game_of_life::Board;
     ///
-    /// let board = Board::from_str(&vec![
-    ///     "#_",
-    ///     "_#",
-    /// ].join("\n"), '#');
+    /// let life = vec![
+    ///     "#Life 1.06",
+    ///     "0 -1",
+    ///     "1 0",
+    ///     "-1 1",
+    ///     "0 1",
+    ///     "1 1",
+    /// ].join("\n");
     ///
-    /// let coords = board.cell_coords();
+    /// let board = Board::from_life_106(&life).unwrap();
     ///
-    /// assert_eq!(coords.len(), 4);
-    /// assert!(coords.contains(&Coord::new(0, 0)));
-    /// assert!(coords.contains(&Coord::new(0, 1)));
-    /// assert!(coords.contains(&Coord::new(1, 0)));
-    /// assert!(coords.contains(&Coord::new(1, 1)));
+    /// assert_eq!(board.to_str('#', '_'), vec![
+    ///     "_#_",
+    ///     "__#",
+    ///     "###",
+    /// ].join("\n"));
+    ///
+    /// assert!(Board::from_life_106("0 0").is_err());
     /// ```
-    pub fn cell_coords(&self) -> &Vec<Coord> {
-        &self.cell_coords
+    ///
+    /// # Errors
+    /// If the header is missing, a line is not a pair of integers, there are no live cells, or
+    /// the live cells span more than 2^24 cells
+    pub fn from_life_106(text: &str) -> Result<Board, ParseError> {
+        let invalid = |row: usize, line: &str| ParseError::InvalidToken {
+            row,
+            column: 0,
+            token: line.to_string(),
+        };
+
+        let mut lines = text.lines().map(str::trim).enumerate();
+        match lines.next() {
+            Some((_, "#Life 1.06")) => {}
+            Some((row, line)) => return Err(invalid(row, line)),
+            None => return Err(ParseError::Empty),
+        }
+
+        let mut live_cells = Vec::new();
+        for (row, line) in lines.filter(|(_, line)| !line.is_empty() && !line.starts_with('#')) {
+            let mut numbers = line.split_whitespace().map(str::parse::<isize>);
+            match (numbers.next(), numbers.next(), numbers.next()) {
+                (Some(Ok(x)), Some(Ok(y)), None) => live_cells.push((x, y)),
+                _ => return Err(invalid(row, line)),
+            }
+        }
+
+        let min_x = live_cells
+            .iter()
+            .map(|&(x, _)| x)
+            .min()
+            .ok_or(ParseError::Empty)?;
+        let min_y = live_cells
+            .iter()
+            .map(|&(_, y)| y)
+            .min()
+            .ok_or(ParseError::Empty)?;
+        let max_x = live_cells
+            .iter()
+            .map(|&(x, _)| x)
+            .max()
+            .ok_or(ParseError::Empty)?;
+        let max_y = live_cells
+            .iter()
+            .map(|&(_, y)| y)
+            .max()
+            .ok_or(ParseError::Empty)?;
+
+        let width = max_x.abs_diff(min_x).checked_add(1);
+        let height = max_y.abs_diff(min_y).checked_add(1);
+        let cells = width.zip(height).and_then(|(w, h)| w.checked_mul(h));
+        let (width, height) = match cells {
+            Some(cells) if cells <= rle::MAX_DECODED_CELLS => (width.unwrap(), height.unwrap()),
+            _ => return Err(ParseError::TooLarge),
+        };
+
+        let mut board = Board::new(width, height);
+        for (x, y) in live_cells {
+            board.revive_cell(&Coord::new(x.abs_diff(min_x), y.abs_diff(min_y)));
+        }
+
+        Ok(board)
     }
 
-    /// Gets a reference to the state of the cell at the given coordinate
+    /// Converts the board to the plaintext `.cells` format, with `O` for live cells and `.` for
+    /// dead cells
     ///
     /// # Examples
     /// ```
-    /// use game_of_life::{Board, Coord, CellState};
+    /// use game_of_life::Board;
     ///
     /// let board = Board::from_str(&vec![
     ///     "#_",
     ///     "_#",
     /// ].join("\n"), '#');
     ///
-    /// let coord = Coord::new(0, 0);
-    ///
-    /// assert_eq!(board.get_cell_state(&coord), &CellState::Alive);
+    /// assert_eq!(board.to_cells(), "O.\n.O");
     /// ```
-    pub fn get_cell_state(&self, coord: &Coord) -> &CellState {
-        &self.cells[coord.y][coord.x]
-    }
-
-    /// Gets a mutable reference to the state of the cell at the given coordinate
-    fn get_cell_state_mut(&mut self, coord: &Coord) -> &mut CellState {
-        &mut self.cells[coord.y][coord.x]
+    pub fn to_cells(&self) -> String {
+        self.to_str('O', '.')
     }
 
-    /// Kills the cell at the given coordinate, making sure it is dead
+    /// Creates a new board from a string where each character is a cell, like
+    /// [`Board::from_str`], but only accepts the given alive and dead characters. Any other
+    /// character is an error, as is a row with a different number of cells than the ones before
+    /// it. A trailing newline is ignored
     ///
     /// # Examples
     /// ```
     /// use game_of_life::{Board, Coord, CellState};
     ///
-    /// let mut board = Board::from_str(&vec![
-    ///     "##",
-    ///     "##",
-    /// ].join("\n"), '#');
+    /// let board = Board::from_str_charset("O..\n.*.\n", &['O', '*'], &['.']).unwrap();
     ///
-    /// let coord = Coord::new(0, 0);
-    /// board.kill_cell(&coord);
+    /// assert_eq!(board.width(), 3);
+    /// assert_eq!(board.height(), 2);
+    /// assert_eq!(board.get_cell_state(&Coord::new(0, 0)), &CellState::Alive);
+    /// assert_eq!(board.get_cell_state(&Coord::new(1, 1)), &CellState::Alive);
+    /// assert_eq!(board.get_cell_state(&Coord::new(2, 1)), &CellState::Dead);
     ///
-    /// assert_eq!(board.get_cell_state(&coord), &CellState::Dead);
+    /// assert!(Board::from_str_charset("O. \n...", &['O'], &['.']).is_err());
     /// ```
-    pub fn kill_cell(&mut self, coord: &Coord) {
-        self.get_cell_state_mut(coord).kill();
+    pub fn from_str_charset(
+        string: &str,
+        alive_chars: &[char],
+        dead_chars: &[char],
+    ) -> Result<Board, ParseError> {
+        let string = string.replace('\r', "");
+        if string.is_empty() {
+            return Err(ParseError::Empty);
+        }
+
+        let mut grid: Vec<Vec<CellState>> = Vec::new();
+
+        for (y, line) in string
+            .strip_suffix('\n')
+            .unwrap_or(&string)
+            .split('\n')
+            .enumerate()
+        {
+            let row = line
+                .chars()
+                .enumerate()
+                .map(|(x, c)| {
+                    if alive_chars.contains(&c) {
+                        Ok(CellState::Alive)
+                    } else if dead_chars.contains(&c) {
+                        Ok(CellState::Dead)
+                    } else {
+                        Err(ParseError::InvalidToken {
+                            row: y,
+                            column: x,
+                            token: c.to_string(),
+                        })
+                    }
+                })
+                .collect::<Result<Vec<CellState>, ParseError>>()?;
+
+            if let Some(first) = grid.first() {
+                if first.len() != row.len() {
+                    return Err(ParseError::RaggedRow {
+                        row: y,
+                        expected: first.len(),
+                        found: row.len(),
+                    });
+                }
+            }
+
+            grid.push(row);
+        }
+
+        if grid[0].is_empty() {
+            return Err(ParseError::Empty);
+        }
+
+        Ok(Board::from_grid(&grid))
     }
 
-    /// Revives the cell at the given coordinate, making sure it is alive
+    /// Creates a new board where each cell is alive with the probability `density`. The cells are
+    /// drawn from a seeded pseudo random generator, so the same seed and dimensions always give
+    /// the same board
     ///
     /// # Examples
     /// ```
-    /// use game_of_life::{Board, Coord, CellState};
-    ///
-    /// let mut board = Board::from_str(&vec![
-    ///     "__",
-    ///     "__",
-    /// ].join("\n"), '#');
+    /// use game_of_life::Board;
     ///
-    /// let coord = Coord::new(0, 0);
-    /// board.revive_cell(&coord);
+    /// let board = Board::random(20, 10, 0.3, 42);
     ///
-    /// assert_eq!(board.get_cell_state(&coord), &CellState::Alive);
+    /// assert_eq!(board, Board::random(20, 10, 0.3, 42));
+    /// assert!(Board::random(20, 10, 0.0, 42).is_empty());
+    /// assert_eq!(Board::random(20, 10, 1.0, 42).population(), 200);
     /// ```
-    pub fn revive_cell(&mut self, coord: &Coord) {
-        self.get_cell_state_mut(coord).revive();
+    ///
+    /// # Panics
+    /// If width or height is 0, or if density is not in the range [0, 1]
+    pub fn random(width: usize, height: usize, density: f64, seed: u64) -> Board {
+        assert!(
+            (0.0..=1.0).contains(&density),
+            "Density must be in the range [0, 1]"
+        );
+
+        let mut board = Board::new(width, height);
+        let mut rng = Rng::new(seed);
+
+        for y in 0..height {
+            for x in 0..width {
+                if rng.next_f64() < density {
+                    board.revive_cell(&Coord::new(x, y));
+                }
+            }
+        }
+
+        board
     }
 
-    /// Toggles the state of the cell at the given coordinate
+    /// Creates a new board with a stream of gliders heading south east. The gliders are placed
+    /// along the diagonal from the top left corner, `spacing` cells apart, wrapping around the
+    /// edges of the board if they do not fit
     ///
     /// # Examples
     /// ```
-    /// use game_of_life::{Board, Coord, CellState};
-    ///
-    /// let mut board = Board::from_str(&vec![
-    ///     "__",
-    ///     "__",
-    /// ].join("\n"), '#');
-    ///
-    /// let coord = Coord::new(0, 0);
-    ///
-    /// assert_eq!(board.get_cell_state(&coord), &CellState::Dead);
-    ///
-    /// board.toggle_cell(&coord);
-    ///
-    /// assert_eq!(board.get_cell_state(&coord), &CellState::Alive);
+    /// use game_of_life::Board;
     ///
-    /// board.toggle_cell(&coord);
+    /// let board = Board::with_glider_stream(20, 20, 3, 5);
     ///
-    /// assert_eq!(board.get_cell_state(&coord), &CellState::Dead);
+    /// assert_eq!(board.population(), 15);
     /// ```
-    pub fn toggle_cell(&mut self, coord: &Coord) {
-        self.get_cell_state_mut(coord).toggle();
+    ///
+    /// # Panics
+    /// If width or height is 0
+    pub fn with_glider_stream(width: usize, height: usize, count: usize, spacing: usize) -> Board {
+        let mut board = Board::new(width, height);
+        let glider = patterns::glider();
+
+        for i in 0..count {
+            let offset = i * spacing;
+
+            for coord in glider.cell_coords() {
+                if glider.get_cell_state(coord) == &CellState::Alive {
+                    board.revive_cell(&Coord::new(
+                        (offset + coord.x) % width,
+                        (offset + coord.y) % height,
+                    ));
+                }
+            }
+        }
+
+        board
     }
 
-    /// Gets the neighbours of a given coord, wrapping around if it is on an edge
+    /// Creates a string representation of the board
     ///
     /// # Examples
     /// ```
-    /// use game_of_life::{Board, Coord};
+    /// use game_of_life::Board;
     ///
-    /// let board = Board::from_str(&vec![
-    ///     "#__",
+    /// let board_str = vec![
+    ///     "#___#",
+    ///     "_#_#_",
+    ///     "__#__",
+    ///     "_#_#_",
+    ///     "#___#",
+    /// ].join("\n");
+    ///
+    /// let board = Board::from_str(&board_str, '#');
+    ///
+    /// assert_eq!(board.to_str('#', '_'), board_str);
+    /// ```
+    pub fn to_str(&self, alive: char, dead: char) -> String {
+        if alive == '\n' || alive == '\r' {
+            panic!("The alive character cannot be a newline character");
+        }
+        if dead == '\n' || dead == '\r' {
+            panic!("The alive character cannot be a newline character");
+        }
+
+        self.cells
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cell_state| match cell_state {
+                        CellState::Alive => alive,
+                        CellState::Dead => dead,
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Creates a string representation of the board for debugging, with the y index in front of
+    /// each row and a ruler with the x indices along the top. Only the last digit of each x index
+    /// is shown. Unlike [`Board::to_str`], the output cannot be parsed back into a board
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::Board;
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "#__",
     ///     "_#_",
     ///     "__#",
     /// ].join("\n"), '#');
     ///
-    /// let coord = Coord::new(0, 0);
-    /// let neighbour_coords = board.get_neighbour_coords(&coord);
+    /// assert_eq!(board.to_debug_str('#', '_'), vec![
+    ///     "  012",
+    ///     "0 #__",
+    ///     "1 _#_",
+    ///     "2 __#",
+    /// ].join("\n"));
+    /// ```
     ///
-    /// assert!(neighbour_coords.contains(&Coord::new(1, 1)));
-    /// assert!(neighbour_coords.contains(&Coord::new(1, board.height() - 1)));
-    /// assert!(neighbour_coords.contains(&Coord::new(board.width() - 1, 1)));
-    /// // and 5 more
+    /// # Panics
+    /// If the alive or dead character is a newline character
+    pub fn to_debug_str(&self, alive: char, dead: char) -> String {
+        let label_width = (self.height() - 1).to_string().len();
+
+        let ruler = (0..self.width())
+            .map(|x| char::from_digit((x % 10) as u32, 10).unwrap())
+            .collect::<String>();
+
+        std::iter::once(format!("{} {}", " ".repeat(label_width), ruler))
+            .chain(
+                self.to_str(alive, dead)
+                    .split('\n')
+                    .enumerate()
+                    .map(|(y, row)| format!("{:>width$} {}", y, row, width = label_width)),
+            )
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Converts the board to a string like [`Board::to_str`], but first moves the live cells
+    /// around the torus so their toroidal bounding box is in the middle of the board. A pattern
+    /// split across the edges of the board is then shown in one piece
+    ///
+    /// # Examples
     /// ```
-    pub fn get_neighbour_coords(&self, coord: &Coord) -> Vec<Coord> {
-        let x = coord.x;
-        let xa = if coord.x == self.width() - 1 {
-            0
-        } else {
-            coord.x + 1
-        };
-        let xs = if coord.x == 0 {
-            self.width() - 1
-        } else {
-            coord.x - 1
+    /// use game_of_life::Board;
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "_____",
+    ///     "#___#",
+    ///     "#___#",
+    ///     "_____",
+    /// ].join("\n"), '#');
+    ///
+    /// assert_eq!(board.to_str_recentered('#', '_'), vec![
+    ///     "_____",
+    ///     "_##__",
+    ///     "_##__",
+    ///     "_____",
+    /// ].join("\n"));
+    /// ```
+    ///
+    /// # Panics
+    /// If the alive or dead character is a newline character
+    pub fn to_str_recentered(&self, alive: char, dead: char) -> String {
+        let Some((top_left, box_width, box_height)) = self.toroidal_bounding_box() else {
+            return self.to_str(alive, dead);
         };
-        let y = coord.y;
-        let ya = if coord.y == self.height() - 1 {
-            0
-        } else {
-            coord.y + 1
+
+        let (width, height) = (self.width(), self.height());
+        let dx = ((width - box_width) / 2 + width - top_left.x) % width;
+        let dy = ((height - box_height) / 2 + height - top_left.y) % height;
+
+        Board::from_fn(width, height, |x, y| {
+            self.get_cell_state(&Coord::new(
+                (x + width - dx) % width,
+                (y + height - dy) % height,
+            ))
+            .clone()
+        })
+        .to_str(alive, dead)
+    }
+
+    /// Creates an image of the board as RGBA pixels, row by row, with each cell drawn as a square
+    /// of `cell_size` by `cell_size` pixels
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Colors};
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "#_",
+    ///     "_#",
+    /// ].join("\n"), '#');
+    ///
+    /// let pixels = board.to_rgba(2, &Colors::default());
+    ///
+    /// // 4 by 4 pixels with 4 bytes each
+    /// assert_eq!(pixels.len(), 64);
+    /// assert_eq!(&pixels[0..4], &[0, 0, 0, 255]);
+    /// assert_eq!(&pixels[8..12], &[255, 255, 255, 255]);
+    /// ```
+    pub fn to_rgba(&self, cell_size: u32, colors: &Colors) -> Vec<u8> {
+        self.to_pixels(cell_size)
+            .into_iter()
+            .flat_map(|pixel| {
+                let [r, g, b] = match pixel {
+                    CellState::Alive => colors.alive,
+                    CellState::Dead => colors.dead,
+                };
+                [r, g, b, 255]
+            })
+            .collect()
+    }
+
+    /// Creates a PNG image of the board like [`Board::to_rgba`], with the number of live
+    /// neighbours of each cell written in its centre using the given font. The numbers are drawn
+    /// in the dead colour on live cells and in the alive colour on dead cells, scaled up to fill
+    /// as much of the cell as they can while leaving a margin. Parts of numbers which do not fit
+    /// in small cells are left out
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Colors, Font};
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "_#_",
+    ///     "_#_",
+    ///     "_#_",
+    /// ].join("\n"), '#');
+    ///
+    /// let png = board.to_annotated_png(14, &Colors::default(), &Font::default()).unwrap();
+    ///
+    /// assert!(png.starts_with(b"\x89PNG"));
+    /// ```
+    ///
+    /// # Errors
+    /// If the image could not be encoded, which happens if it is too large for PNG
+    #[cfg(feature = "png")]
+    pub fn to_annotated_png(
+        &self,
+        cell_size: u32,
+        colors: &Colors,
+        font: &Font,
+    ) -> io::Result<Vec<u8>> {
+        let cell_size = cell_size as usize;
+        let image_width = self.width() * cell_size;
+        let mut pixels = self.to_rgba(cell_size as u32, colors);
+
+        let scale = (cell_size / (font.width().max(font.height()) + 2)).max(1);
+        let left_margin = cell_size.saturating_sub(font.width() * scale) / 2;
+        let top_margin = cell_size.saturating_sub(font.height() * scale) / 2;
+
+        for coord in &self.cell_coords {
+            let count = self.get_live_neighbours_of(coord) as u32;
+            let Some(glyph) = char::from_digit(count, 10).and_then(|c| font.glyph(c)) else {
+                continue;
+            };
+
+            let [r, g, b] = match self.get_cell_state(coord) {
+                CellState::Alive => colors.dead,
+                CellState::Dead => colors.alive,
+            };
+
+            for (glyph_y, row) in glyph.iter().enumerate() {
+                for (glyph_x, _) in row.iter().enumerate().filter(|(_, &pixel)| pixel) {
+                    for dy in 0..scale {
+                        for dx in 0..scale {
+                            let x = left_margin + glyph_x * scale + dx;
+                            let y = top_margin + glyph_y * scale + dy;
+                            if x >= cell_size || y >= cell_size {
+                                continue;
+                            }
+
+                            let x = coord.x * cell_size + x;
+                            let y = coord.y * cell_size + y;
+                            let i = (y * image_width + x) * 4;
+                            pixels[i..i + 4].copy_from_slice(&[r, g, b, 255]);
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut png = Vec::new();
+        let mut encoder = png::Encoder::new(
+            &mut png,
+            image_width as u32,
+            (self.height() * cell_size) as u32,
+        );
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header().map_err(io::Error::other)?;
+        writer.write_image_data(&pixels).map_err(io::Error::other)?;
+        writer.finish().map_err(io::Error::other)?;
+
+        Ok(png)
+    }
+
+    /// Scales the board up to one cell state per pixel, row by row, with each cell covering a
+    /// square of `cell_size` by `cell_size` pixels
+    pub(crate) fn to_pixels(&self, cell_size: u32) -> Vec<CellState> {
+        let cell_size = cell_size as usize;
+
+        self.cells
+            .iter()
+            .flat_map(|row| {
+                let pixel_row: Vec<CellState> = row
+                    .iter()
+                    .flat_map(|cell_state| std::iter::repeat(cell_state.clone()).take(cell_size))
+                    .collect();
+                std::iter::repeat(pixel_row).take(cell_size).flatten()
+            })
+            .collect()
+    }
+
+    /// Creates an SVG image of the board, with a background in the dead colour and a square in
+    /// the alive colour for each live cell. The colours can be anything SVG accepts as a fill
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::Board;
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "#_",
+    ///     "_#",
+    /// ].join("\n"), '#');
+    ///
+    /// let svg = board.to_svg(10, "black", "white");
+    ///
+    /// assert!(svg.starts_with("<svg"));
+    /// assert!(svg.contains(r#"viewBox="0 0 20 20""#));
+    /// assert!(svg.contains(r#"<rect x="10" y="10" width="10" height="10" fill="black"/>"#));
+    /// ```
+    pub fn to_svg(&self, cell_size: u32, alive_fill: &str, dead_fill: &str) -> String {
+        let width = self.width() as u32 * cell_size;
+        let height = self.height() as u32 * cell_size;
+
+        let mut svg = vec![
+            format!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+                w = width,
+                h = height
+            ),
+            format!(
+                r#"<rect x="0" y="0" width="{}" height="{}" fill="{}"/>"#,
+                width, height, dead_fill
+            ),
+        ];
+
+        for coord in &self.cell_coords {
+            if self.get_cell_state(coord) == &CellState::Alive {
+                svg.push(format!(
+                    r#"<rect x="{}" y="{}" width="{s}" height="{s}" fill="{}"/>"#,
+                    coord.x as u32 * cell_size,
+                    coord.y as u32 * cell_size,
+                    alive_fill,
+                    s = cell_size
+                ));
+            }
+        }
+
+        svg.push("</svg>".to_string());
+
+        svg.join("\n")
+    }
+
+    /// Creates a comma separated representation of the board, with `1` for live cells and `0` for
+    /// dead cells. Each row is on its own line
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::Board;
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "#_",
+    ///     "_#",
+    /// ].join("\n"), '#');
+    ///
+    /// assert_eq!(board.to_csv(), "1,0\n0,1");
+    /// ```
+    pub fn to_csv(&self) -> String {
+        self.cells
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cell_state| match cell_state {
+                        CellState::Alive => "1",
+                        CellState::Dead => "0",
+                    })
+                    .collect::<Vec<&str>>()
+                    .join(",")
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Creates a new board from comma separated values, where `1` is a live cell and `0` is a dead
+    /// cell. All rows must have the same number of cells. A trailing newline is ignored
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Coord, CellState};
+    ///
+    /// let board = Board::from_csv("1,0,0\n0,1,1\n").unwrap();
+    ///
+    /// assert_eq!(board.width(), 3);
+    /// assert_eq!(board.height(), 2);
+    /// assert_eq!(board.get_cell_state(&Coord::new(0, 0)), &CellState::Alive);
+    /// assert_eq!(board.get_cell_state(&Coord::new(1, 1)), &CellState::Alive);
+    /// assert_eq!(board.get_cell_state(&Coord::new(2, 0)), &CellState::Dead);
+    ///
+    /// assert!(Board::from_csv("1,0\n1").is_err());
+    /// assert!(Board::from_csv("1,2").is_err());
+    /// ```
+    pub fn from_csv(text: &str) -> Result<Board, ParseError> {
+        let text = text.replace('\r', "");
+        if text.trim().is_empty() {
+            return Err(ParseError::Empty);
+        }
+
+        let mut grid: Vec<Vec<CellState>> = Vec::new();
+
+        for (y, line) in text
+            .strip_suffix('\n')
+            .unwrap_or(&text)
+            .split('\n')
+            .enumerate()
+        {
+            let row = line
+                .split(',')
+                .enumerate()
+                .map(|(x, token)| match token.trim() {
+                    "1" => Ok(CellState::Alive),
+                    "0" => Ok(CellState::Dead),
+                    _ => Err(ParseError::InvalidToken {
+                        row: y,
+                        column: x,
+                        token: token.to_string(),
+                    }),
+                })
+                .collect::<Result<Vec<CellState>, ParseError>>()?;
+
+            if let Some(first) = grid.first() {
+                if first.len() != row.len() {
+                    return Err(ParseError::RaggedRow {
+                        row: y,
+                        expected: first.len(),
+                        found: row.len(),
+                    });
+                }
+            }
+
+            grid.push(row);
+        }
+
+        Ok(Board::from_grid(&grid))
+    }
+
+    /// Creates a new board from a Run Length Encoded pattern, the format most patterns are shared
+    /// in. The board gets the size given in the `x = .., y = ..` header line. The rule in the
+    /// header is checked, but is not kept as boards have no rules. Comment lines starting with `#`
+    /// are skipped. Headers describing more than 2^24 cells are rejected rather than allocated
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Coord, CellState};
+    ///
+    /// let rle = vec![
+    ///     "#N Glider",
+    ///     "x = 3, y = 3, rule = B3/S23",
+    ///     "bo$2bo$3o!",
+    /// ].join("\n");
+    ///
+    /// let board = Board::from_rle(&rle).unwrap();
+    ///
+    /// assert_eq!(board.to_str('#', '_'), vec![
+    ///     "_#_",
+    ///     "__#",
+    ///     "###",
+    /// ].join("\n"));
+    ///
+    /// assert!(Board::from_rle("bo$2bo$3o!").is_err());
+    /// ```
+    pub fn from_rle(rle: &str) -> Result<Board, RleError> {
+        rle::decode(rle)
+    }
+
+    /// Reads the width and height of the board in a Run Length Encoded pattern from its header,
+    /// without decoding the pattern itself. This is a cheap way to check the size of a pattern
+    /// before loading it
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::Board;
+    ///
+    /// let rle = vec![
+    ///     "#N Glider",
+    ///     "x = 3, y = 3, rule = B3/S23",
+    ///     "bo$2bo$3o!",
+    /// ].join("\n");
+    ///
+    /// assert_eq!(Board::rle_dimensions(&rle), Ok((3, 3)));
+    /// ```
+    ///
+    /// # Errors
+    /// If the header is missing or invalid, or gives a width or height of 0
+    pub fn rle_dimensions(rle: &str) -> Result<(usize, usize), RleError> {
+        rle::dimensions(rle)
+    }
+
+    /// Creates a new board from an apgcode, the identifier Catagolue uses for still lifes (`xs`),
+    /// oscillators (`xp`) and spaceships (`xq`). The board is the size of the pattern. Patterns
+    /// spanning more than 2^24 cells are rejected before the board is allocated
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::Board;
+    ///
+    /// let board = Board::from_apgcode("xq4_153").unwrap();
+    ///
+    /// assert_eq!(board.to_str('#', '_'), vec![
+    ///     "###",
+    ///     "__#",
+    ///     "_#_",
+    /// ].join("\n"));
+    ///
+    /// assert!(Board::from_apgcode("xs4_3!").is_err());
+    /// ```
+    pub fn from_apgcode(code: &str) -> Result<Board, ParseError> {
+        let invalid = |column: usize, token: &str| ParseError::InvalidToken {
+            row: 0,
+            column,
+            token: token.to_string(),
         };
-        let ys = if coord.y == 0 {
-            self.height() - 1
-        } else {
-            coord.y - 1
+
+        // The prefix is the kind of pattern and its period or population, like `xp2_`
+        let (prefix, strips) = code.split_once('_').ok_or_else(|| invalid(0, code))?;
+        let valid_prefix = ["xs", "xp", "xq"].contains(&prefix.get(..2).unwrap_or(""))
+            && prefix.len() > 2
+            && prefix[2..].chars().all(|c| c.is_ascii_digit());
+        if !valid_prefix {
+            return Err(invalid(0, prefix));
+        }
+
+        // Only lowercase letters and digits are used, unlike what `char::to_digit` accepts
+        let digit = |column: usize, c: char, radix: u32| {
+            Some(c)
+                .filter(|c| c.is_ascii_digit() || c.is_ascii_lowercase())
+                .and_then(|c| c.to_digit(radix))
+                .ok_or_else(|| invalid(column, &c.to_string()))
         };
 
-        vec![
-            Coord::new(xs, y),  // West
-            Coord::new(xa, y),  // East
-            Coord::new(x, ys),  // North
-            Coord::new(x, ya),  // South
-            Coord::new(xs, ys), // North West
-            Coord::new(xa, ys), // North East
-            Coord::new(xs, ya), // South West
-            Coord::new(xa, ya), // South East
-        ]
+        // Each character is a column of five cells in a strip, with the lowest bit at the top
+        let mut live_cells = Vec::new();
+        let (mut strip, mut x) = (0, 0);
+        let (mut width, mut height) = (0, 0);
+        let mut chars = strips
+            .char_indices()
+            .map(|(i, c)| (prefix.len() + 1 + i, c));
+
+        while let Some((column, c)) = chars.next() {
+            match c {
+                'w' => x += 2,
+                'x' => x += 3,
+                'y' => {
+                    let (column, c) = chars.next().ok_or_else(|| invalid(column, "y"))?;
+                    x += 4 + digit(column, c, 36)? as usize;
+                }
+                'z' => {
+                    strip += 1;
+                    x = 0;
+                }
+                c => {
+                    let bits = digit(column, c, 32)?;
+                    for bit in (0..5).filter(|bit| bits & (1 << bit) != 0) {
+                        width = width.max(x + 1);
+                        height = height.max(strip * 5 + bit + 1);
+                        let cells = width.checked_mul(height);
+                        if cells.map_or(true, |cells| cells > rle::MAX_DECODED_CELLS) {
+                            return Err(ParseError::TooLarge);
+                        }
+                        live_cells.push(Coord::new(x, strip * 5 + bit));
+                    }
+                    x += 1;
+                }
+            }
+        }
+
+        if live_cells.is_empty() {
+            return Err(ParseError::Empty);
+        }
+
+        let mut board = Board::new(width, height);
+        for coord in &live_cells {
+            board.revive_cell(coord);
+        }
+
+        Ok(board)
     }
 
-    /// Gets the count of live neighbours of a cell
+    /// Creates a Run Length Encoded representation of the whole board, including dead cells
+    /// around the pattern. The header has no rule, as boards have no rules. Dead cells at the end
+    /// of a row and empty rows at the end of the board are left out of the body, as the header
+    /// gives the size
     ///
     /// # Examples
     /// ```
-    /// use game_of_life::{Board, Coord, CellState};
+    /// use game_of_life::Board;
     ///
     /// let board = Board::from_str(&vec![
-    ///     "_____",
-    ///     "_##__",
-    ///     "_____",
+    ///     "_#___",
     ///     "__#__",
+    ///     "###__",
     ///     "_____",
     /// ].join("\n"), '#');
     ///
-    /// let coord = Coord::new(2, 2);
+    /// assert_eq!(board.to_rle(), "x = 5, y = 4\nbo$2bo$3o!");
+    /// assert_eq!(Board::from_rle(&board.to_rle()).unwrap(), board);
+    /// ```
+    pub fn to_rle(&self) -> String {
+        [
+            rle::dimensions_header(self.width(), self.height()),
+            rle::encode(self.cells.iter().map(Vec::as_slice)),
+        ]
+        .join("\n")
+    }
+
+    /// Creates a Run Length Encoded representation of the board, with `#N` name and `#O` origin
+    /// comments. The pattern is trimmed to the bounding box of its live cells. An empty board is
+    /// written as a single dead cell, as RLE readers reject patterns with no rows or columns
     ///
-    /// assert_eq!(board.get_live_neighbours_of(&coord), 3);
+    /// # Examples
     /// ```
-    pub fn get_live_neighbours_of(&self, coord: &Coord) -> u8 {
-        self.get_neighbour_coords(coord)
+    /// use game_of_life::{Board, rules};
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "_____",
+    ///     "__#__",
+    ///     "___#_",
+    ///     "_###_",
+    ///     "_____",
+    /// ].join("\n"), '#');
+    ///
+    /// let rle = board.to_rle_named("Glider", &rules::conways());
+    /// let lines: Vec<&str> = rle.lines().collect();
+    ///
+    /// assert_eq!(lines[0], "#N Glider");
+    /// assert!(lines[1].starts_with("#O "));
+    /// assert_eq!(lines[2], "x = 3, y = 3, rule = B3/S23");
+    /// assert_eq!(lines[3], "bo$2bo$3o!");
+    /// ```
+    pub fn to_rle_named(&self, name: &str, rules: &Rules) -> String {
+        [
+            format!("#N {}", name),
+            format!(
+                "#O {} {}",
+                env!("CARGO_PKG_NAME"),
+                env!("CARGO_PKG_VERSION")
+            ),
+            self.to_trimmed_rle(rules),
+        ]
+        .join("\n")
+    }
+
+    /// Creates the header and body of a Run Length Encoded representation of the board, trimmed
+    /// to the bounding box of its live cells, or as a single dead cell if there are none
+    pub(crate) fn to_trimmed_rle(&self, rules: &Rules) -> String {
+        let (width, height, body) = match self.live_bounds() {
+            Some((min, max)) => (
+                max.x - min.x + 1,
+                max.y - min.y + 1,
+                rle::encode(
+                    self.cells[min.y..=max.y]
+                        .iter()
+                        .map(|row| &row[min.x..=max.x]),
+                ),
+            ),
+            None => (1, 1, rle::encode(std::iter::empty())),
+        };
+
+        [rle::header(width, height, rules), body].join("\n")
+    }
+
+    /// Creates a short code for sharing the board, for example in chat or in a URL. The pattern
+    /// is trimmed to the bounding box of its live cells, packed as bits, compressed and encoded
+    /// as URL safe base64. An empty board is shared as a single dead cell
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, patterns};
+    ///
+    /// let code = patterns::glider().to_share_code();
+    ///
+    /// assert!(code.len() < 10);
+    /// assert_eq!(Board::from_share_code(&code), Ok(patterns::glider()));
+    /// ```
+    pub fn to_share_code(&self) -> String {
+        match self.live_bounds() {
+            Some((min, max)) => share_code::encode(
+                max.x - min.x + 1,
+                max.y - min.y + 1,
+                self.cells[min.y..=max.y]
+                    .iter()
+                    .map(|row| &row[min.x..=max.x]),
+            ),
+            None => share_code::encode(1, 1, std::iter::once(&[CellState::Dead][..])),
+        }
+    }
+
+    /// Creates a new board from a code made by [`Board::to_share_code`]
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, DecodeError};
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "#_",
+    ///     "_#",
+    /// ].join("\n"), '#');
+    ///
+    /// assert_eq!(Board::from_share_code(&board.to_share_code()), Ok(board));
+    /// assert_eq!(Board::from_share_code("not valid!"), Err(DecodeError::InvalidCharacter(' ')));
+    /// ```
+    pub fn from_share_code(code: &str) -> Result<Board, DecodeError> {
+        share_code::decode(code)
+    }
+
+    /// The width of the board
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::Board;
+    ///
+    /// let width = 15;
+    /// let height = 10;
+    /// let board = Board::new(width, height);
+    ///
+    /// assert_eq!(board.width(), width);
+    /// ```
+    pub fn width(&self) -> usize {
+        self.cells[0].len()
+    }
+
+    /// The height of the board
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::Board;
+    ///
+    /// let width = 15;
+    /// let height = 10;
+    /// let board = Board::new(width, height);
+    ///
+    /// assert_eq!(board.height(), height);
+    /// ```
+    pub fn height(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// How the edges of the board behave. New boards are toroidal
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Boundary};
+    ///
+    /// let board = Board::new(15, 10);
+    ///
+    /// assert_eq!(board.boundary(), Boundary::Toroidal);
+    /// ```
+    pub fn boundary(&self) -> Boundary {
+        self.boundary
+    }
+
+    /// Sets how the edges of the board behave
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Boundary};
+    ///
+    /// let mut board = Board::new(15, 10);
+    /// board.set_boundary(Boundary::Dead);
+    ///
+    /// assert_eq!(board.boundary(), Boundary::Dead);
+    /// ```
+    pub fn set_boundary(&mut self, boundary: Boundary) {
+        self.boundary = boundary;
+    }
+
+    /// Which cells around a cell count as its neighbours. New boards use the Moore neighbourhood
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Neighbourhood};
+    ///
+    /// let board = Board::new(15, 10);
+    ///
+    /// assert_eq!(board.neighbourhood(), Neighbourhood::Moore);
+    /// ```
+    pub fn neighbourhood(&self) -> Neighbourhood {
+        self.neighbourhood
+    }
+
+    /// Sets which cells around a cell count as its neighbours
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Coord, Neighbourhood};
+    ///
+    /// let mut board = Board::new(5, 5);
+    /// board.set_neighbourhood(Neighbourhood::VonNeumann);
+    ///
+    /// assert_eq!(board.get_neighbour_coords(&Coord::new(2, 2)).len(), 4);
+    /// ```
+    pub fn set_neighbourhood(&mut self, neighbourhood: Neighbourhood) {
+        self.neighbourhood = neighbourhood;
+    }
+
+    /// Set of all cell coordinates on the board
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Coord};
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "#_",
+    ///     "_#",
+    /// ].join("\n"), '#');
+    ///
+    /// let coords = board.cell_coords();
+    ///
+    /// assert_eq!(coords.len(), 4);
+    /// assert!(coords.contains(&Coord::new(0, 0)));
+    /// assert!(coords.contains(&Coord::new(0, 1)));
+    /// assert!(coords.contains(&Coord::new(1, 0)));
+    /// assert!(coords.contains(&Coord::new(1, 1)));
+    /// ```
+    pub fn cell_coords(&self) -> &Vec<Coord> {
+        &self.cell_coords
+    }
+
+    /// Iterates over the coordinates of the live cells on the board, row by row. The states are
+    /// checked as the iterator advances, so nothing is allocated
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Coord};
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "#__",
+    ///     "__#",
+    /// ].join("\n"), '#');
+    ///
+    /// let live: Vec<&Coord> = board.live_cells().collect();
+    ///
+    /// assert_eq!(live, vec![&Coord::new(0, 0), &Coord::new(2, 1)]);
+    /// ```
+    pub fn live_cells(&self) -> impl Iterator<Item = &Coord> {
+        self.cell_coords
             .iter()
             .filter(|c| self.get_cell_state(c) == &CellState::Alive)
-            .count() as u8
     }
-}
 
-#[cfg(test)]
-mod test {
-    use super::*;
+    /// Gets a reference to the state of the cell at the given coordinate
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Coord, CellState};
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "#_",
+    ///     "_#",
+    /// ].join("\n"), '#');
+    ///
+    /// let coord = Coord::new(0, 0);
+    ///
+    /// assert_eq!(board.get_cell_state(&coord), &CellState::Alive);
+    /// ```
+    pub fn get_cell_state(&self, coord: &Coord) -> &CellState {
+        &self.cells[coord.y][coord.x]
+    }
+
+    /// Gets a reference to the state of the cell at the given coordinate, or `None` if the
+    /// coordinate is outside the board
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Coord, CellState};
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "#_",
+    ///     "_#",
+    /// ].join("\n"), '#');
+    ///
+    /// assert_eq!(board.try_get_cell_state(&Coord::new(1, 1)), Some(&CellState::Alive));
+    /// assert_eq!(board.try_get_cell_state(&Coord::new(2, 1)), None);
+    /// ```
+    pub fn try_get_cell_state(&self, coord: &Coord) -> Option<&CellState> {
+        self.cells.get(coord.y)?.get(coord.x)
+    }
+
+    /// Gets a mutable reference to the state of the cell at the given coordinate. The cell can be
+    /// changed freely through the reference, so the live count has to be counted again afterwards
+    fn get_cell_state_mut(&mut self, coord: &Coord) -> &mut CellState {
+        self.live_count = None;
+
+        &mut self.cells[coord.y][coord.x]
+    }
+
+    /// Changes the state of the cell at the given coordinate with the closure, keeping the live
+    /// count up to date
+    fn update_cell<F: FnOnce(&mut CellState)>(&mut self, coord: &Coord, f: F) {
+        let live_count = self.population();
+        let cell_state = &mut self.cells[coord.y][coord.x];
+
+        let was_alive = cell_state == &CellState::Alive;
+        f(cell_state);
+        let is_alive = cell_state == &CellState::Alive;
+
+        self.live_count = Some(live_count + is_alive as usize - was_alive as usize);
+    }
+
+    /// Sets the state of the cell at the given coordinate
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Coord, CellState};
+    ///
+    /// let mut board = Board::new(2, 2);
+    ///
+    /// let coord = Coord::new(1, 0);
+    /// board.set_cell_state(&coord, CellState::Alive);
+    ///
+    /// assert_eq!(board.get_cell_state(&coord), &CellState::Alive);
+    /// ```
+    pub fn set_cell_state(&mut self, coord: &Coord, state: CellState) {
+        self.update_cell(coord, |cell_state| *cell_state = state);
+    }
+
+    /// Checks whether the coordinate is on the board
+    fn contains(&self, coord: &Coord) -> bool {
+        coord.x < self.width() && coord.y < self.height()
+    }
+
+    /// Counts the live cells on the board. The count is kept up to date as cells change, so this
+    /// does not have to look at every cell
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::Board;
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "#_#",
+    ///     "_#_",
+    /// ].join("\n"), '#');
+    ///
+    /// assert_eq!(board.population(), 3);
+    /// assert_eq!(Board::new(3, 3).population(), 0);
+    /// ```
+    pub fn population(&self) -> usize {
+        self.live_count.unwrap_or_else(|| self.live_cells().count())
+    }
+
+    /// Checks whether the board has no live cells, which is the same as having a population of
+    /// 0. Stops looking at the first live cell found
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Coord};
+    ///
+    /// let mut board = Board::new(3, 3);
+    ///
+    /// assert!(board.is_empty());
+    ///
+    /// board.revive_cell(&Coord::new(1, 1));
+    ///
+    /// assert!(!board.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.population() == 0
+    }
+
+    /// Finds the smallest and largest coordinates containing live cells, or `None` if there are
+    /// no live cells. Together they give the bounding box of the live part of the board
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Coord};
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "______",
+    ///     "__#___",
+    ///     "____#_",
+    ///     "___#__",
+    ///     "______",
+    /// ].join("\n"), '#');
+    ///
+    /// assert_eq!(board.live_bounds(), Some((Coord::new(2, 1), Coord::new(4, 3))));
+    /// assert_eq!(Board::new(3, 3).live_bounds(), None);
+    /// ```
+    pub fn live_bounds(&self) -> Option<(Coord, Coord)> {
+        self.live_cells().fold(None, |extent, c| match extent {
+            None => Some((c.clone(), c.clone())),
+            Some((min, max)) => Some((
+                Coord::new(min.x.min(c.x), min.y.min(c.y)),
+                Coord::new(max.x.max(c.x), max.y.max(c.y)),
+            )),
+        })
+    }
+
+    /// Kills the cell at the given coordinate, making sure it is dead
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Coord, CellState};
+    ///
+    /// let mut board = Board::from_str(&vec![
+    ///     "##",
+    ///     "##",
+    /// ].join("\n"), '#');
+    ///
+    /// let coord = Coord::new(0, 0);
+    /// board.kill_cell(&coord);
+    ///
+    /// assert_eq!(board.get_cell_state(&coord), &CellState::Dead);
+    /// ```
+    pub fn kill_cell(&mut self, coord: &Coord) {
+        self.update_cell(coord, CellState::kill);
+    }
+
+    /// Revives the cell at the given coordinate, making sure it is alive
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Coord, CellState};
+    ///
+    /// let mut board = Board::from_str(&vec![
+    ///     "__",
+    ///     "__",
+    /// ].join("\n"), '#');
+    ///
+    /// let coord = Coord::new(0, 0);
+    /// board.revive_cell(&coord);
+    ///
+    /// assert_eq!(board.get_cell_state(&coord), &CellState::Alive);
+    /// ```
+    pub fn revive_cell(&mut self, coord: &Coord) {
+        self.update_cell(coord, CellState::revive);
+    }
+
+    /// Toggles the state of the cell at the given coordinate
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Coord, CellState};
+    ///
+    /// let mut board = Board::from_str(&vec![
+    ///     "__",
+    ///     "__",
+    /// ].join("\n"), '#');
+    ///
+    /// let coord = Coord::new(0, 0);
+    ///
+    /// assert_eq!(board.get_cell_state(&coord), &CellState::Dead);
+    ///
+    /// board.toggle_cell(&coord);
+    ///
+    /// assert_eq!(board.get_cell_state(&coord), &CellState::Alive);
+    ///
+    /// board.toggle_cell(&coord);
+    ///
+    /// assert_eq!(board.get_cell_state(&coord), &CellState::Dead);
+    /// ```
+    pub fn toggle_cell(&mut self, coord: &Coord) {
+        self.update_cell(coord, CellState::toggle);
+    }
+
+    /// Kills the cell at the given coordinate if it is on the board. Returns whether the
+    /// coordinate is on the board
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Coord, CellState};
+    ///
+    /// let mut board = Board::from_str("##", '#');
+    ///
+    /// assert!(board.try_kill_cell(&Coord::new(1, 0)));
+    /// assert_eq!(board.get_cell_state(&Coord::new(1, 0)), &CellState::Dead);
+    ///
+    /// assert!(!board.try_kill_cell(&Coord::new(2, 0)));
+    /// ```
+    pub fn try_kill_cell(&mut self, coord: &Coord) -> bool {
+        let on_board = self.contains(coord);
+        if on_board {
+            self.update_cell(coord, CellState::kill);
+        }
+
+        on_board
+    }
+
+    /// Revives the cell at the given coordinate if it is on the board. Returns whether the
+    /// coordinate is on the board
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Coord, CellState};
+    ///
+    /// let mut board = Board::new(2, 1);
+    ///
+    /// assert!(board.try_revive_cell(&Coord::new(1, 0)));
+    /// assert_eq!(board.get_cell_state(&Coord::new(1, 0)), &CellState::Alive);
+    ///
+    /// assert!(!board.try_revive_cell(&Coord::new(0, 1)));
+    /// ```
+    pub fn try_revive_cell(&mut self, coord: &Coord) -> bool {
+        let on_board = self.contains(coord);
+        if on_board {
+            self.update_cell(coord, CellState::revive);
+        }
+
+        on_board
+    }
+
+    /// Toggles the state of the cell at the given coordinate if it is on the board. Returns
+    /// whether the coordinate is on the board
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Coord, CellState};
+    ///
+    /// let mut board = Board::new(2, 1);
+    ///
+    /// assert!(board.try_toggle_cell(&Coord::new(0, 0)));
+    /// assert_eq!(board.get_cell_state(&Coord::new(0, 0)), &CellState::Alive);
+    ///
+    /// assert!(!board.try_toggle_cell(&Coord::new(5, 5)));
+    /// ```
+    pub fn try_toggle_cell(&mut self, coord: &Coord) -> bool {
+        let on_board = self.contains(coord);
+        if on_board {
+            self.update_cell(coord, CellState::toggle);
+        }
+
+        on_board
+    }
+
+    /// Gets the neighbours of a given coord in the board's neighbourhood. On a toroidal board,
+    /// neighbours of cells on an edge wrap around to the other side. On a board with a dead
+    /// boundary, neighbours outside the board are left out
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Boundary, Coord};
+    ///
+    /// let mut board = Board::from_str(&vec![
+    ///     "#__",
+    ///     "_#_",
+    ///     "__#",
+    /// ].join("\n"), '#');
+    ///
+    /// let coord = Coord::new(0, 0);
+    /// let neighbour_coords = board.get_neighbour_coords(&coord);
+    ///
+    /// assert!(neighbour_coords.contains(&Coord::new(1, 1)));
+    /// assert!(neighbour_coords.contains(&Coord::new(1, board.height() - 1)));
+    /// assert!(neighbour_coords.contains(&Coord::new(board.width() - 1, 1)));
+    /// // and 5 more
+    ///
+    /// board.set_boundary(Boundary::Dead);
+    ///
+    /// assert_eq!(board.get_neighbour_coords(&coord).len(), 3);
+    /// ```
+    pub fn get_neighbour_coords(&self, coord: &Coord) -> Vec<Coord> {
+        let (mut neighbours, diagonal) = self.split_neighbour_coords(coord);
+        if self.neighbourhood == Neighbourhood::Moore {
+            neighbours.extend(diagonal);
+        }
+
+        neighbours
+    }
+
+    /// Gets the orthogonal (west, east, north, south) and the diagonal neighbours of a given
+    /// coord, following the boundary of the board
+    fn split_neighbour_coords(&self, coord: &Coord) -> (Vec<Coord>, Vec<Coord>) {
+        let coords = |offsets: [(isize, isize); 4]| -> Vec<Coord> {
+            offsets
+                .iter()
+                .filter_map(|&(dx, dy)| self.neighbour(coord, dx, dy))
+                .collect()
+        };
+
+        let (mut orthogonal, mut diagonal) = (
+            coords([
+                (-1, 0), // West
+                (1, 0),  // East
+                (0, -1), // North
+                (0, 1),  // South
+            ]),
+            coords([
+                (-1, -1), // North West
+                (1, -1),  // North East
+                (-1, 1),  // South West
+                (1, 1),   // South East
+            ]),
+        );
+
+        // On a projective plane, opposite corners are the same point, so a corner cell reaches the
+        // opposite corner across both edges, and may even reach itself. Each neighbour is kept
+        // once, and the cell is never its own neighbour
+        if self.boundary == Boundary::ProjectivePlane {
+            let mut seen = vec![coord.clone()];
+            let mut unseen = |c: &Coord| {
+                let new = !seen.contains(c);
+                if new {
+                    seen.push(c.clone());
+                }
+                new
+            };
+            orthogonal.retain(&mut unseen);
+            diagonal.retain(&mut unseen);
+        }
+
+        (orthogonal, diagonal)
+    }
+
+    /// Moves one step from a coord by the given offsets, wrapping around or falling off the edges
+    /// depending on the boundary. Wrapping around an edge joined with a twist mirrors the position
+    /// along the other axis. On a projective plane, a diagonal step out of a corner crosses both
+    /// twisted edges at the point where all four corners meet, and leads nowhere
+    fn neighbour(&self, coord: &Coord, dx: isize, dy: isize) -> Option<Coord> {
+        let width = self.width() as isize;
+        let height = self.height() as isize;
+        let mut x = coord.x as isize + dx;
+        let mut y = coord.y as isize + dy;
+
+        if self.boundary == Boundary::ProjectivePlane
+            && !(0..width).contains(&x)
+            && !(0..height).contains(&y)
+        {
+            return None;
+        }
+
+        if !(0..width).contains(&x) {
+            if self.boundary == Boundary::Dead {
+                return None;
+            }
+            x = x.rem_euclid(width);
+            if self.boundary.mirrors_horizontal_wrap() {
+                y = height - 1 - y;
+            }
+        }
+
+        if !(0..height).contains(&y) {
+            if self.boundary == Boundary::Dead {
+                return None;
+            }
+            y = y.rem_euclid(height);
+            if self.boundary.mirrors_vertical_wrap() {
+                x = width - 1 - x;
+            }
+        }
+
+        Some(Coord::new(x as usize, y as usize))
+    }
+
+    /// Checks whether any of the neighbours of a cell are found by wrapping around an edge of the
+    /// board. This is the case for cells on an edge of a board with any boundary joining its edges,
+    /// while no neighbours wrap on a board with a dead boundary
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Boundary, Coord};
+    ///
+    /// let mut board = Board::new(5, 5);
+    ///
+    /// assert!(!board.neighbours_wrapped(&Coord::new(2, 2)));
+    /// assert!(board.neighbours_wrapped(&Coord::new(4, 2)));
+    ///
+    /// board.set_boundary(Boundary::Dead);
+    ///
+    /// assert!(!board.neighbours_wrapped(&Coord::new(4, 2)));
+    /// ```
+    pub fn neighbours_wrapped(&self, coord: &Coord) -> bool {
+        let on_edge = coord.x == 0
+            || coord.y == 0
+            || coord.x == self.width() - 1
+            || coord.y == self.height() - 1;
+
+        on_edge && self.boundary != Boundary::Dead
+    }
+
+    /// Gets the count of live neighbours of a cell
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Coord, CellState};
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "_____",
+    ///     "_##__",
+    ///     "_____",
+    ///     "__#__",
+    ///     "_____",
+    /// ].join("\n"), '#');
+    ///
+    /// let coord = Coord::new(2, 2);
+    ///
+    /// assert_eq!(board.get_live_neighbours_of(&coord), 3);
+    /// ```
+    pub fn get_live_neighbours_of(&self, coord: &Coord) -> u8 {
+        self.get_neighbour_coords(coord)
+            .iter()
+            .filter(|c| self.get_cell_state(c) == &CellState::Alive)
+            .count() as u8
+    }
+
+    /// Counts the live neighbours of every cell at once, in the same order as
+    /// [`Board::cell_coords`]. Each live cell adds one to the counts of its neighbours, so dead
+    /// parts of the board cost next to nothing
+    pub(crate) fn live_neighbour_counts(&self) -> Vec<u8> {
+        let mut counts = vec![0; self.cell_coords.len()];
+
+        for coord in self.live_cells() {
+            for neighbour in self.get_neighbour_coords(coord) {
+                counts[neighbour.y * self.width() + neighbour.x] += 1;
+            }
+        }
+
+        counts
+    }
+
+    /// Counts the live neighbours of a cell separately for the four orthogonal neighbours (north,
+    /// east, south and west) and the four diagonal neighbours. Returns the orthogonal count first.
+    /// The diagonal neighbours are counted even if the board uses the von Neumann neighbourhood
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Coord};
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "_____",
+    ///     "_#_#_",
+    ///     "__#__",
+    ///     "_##__",
+    ///     "_____",
+    /// ].join("\n"), '#');
+    ///
+    /// assert_eq!(board.neighbour_breakdown(&Coord::new(2, 2)), (1, 3));
+    /// ```
+    pub fn neighbour_breakdown(&self, coord: &Coord) -> (u8, u8) {
+        let (orthogonal, diagonal) = self.split_neighbour_coords(coord);
+
+        let count = |coords: &[Coord]| {
+            coords
+                .iter()
+                .filter(|c| self.get_cell_state(c) == &CellState::Alive)
+                .count() as u8
+        };
+
+        (count(&orthogonal), count(&diagonal))
+    }
+
+    /// Counts the live cells among the four diagonal neighbours of a cell, which is the diagonal
+    /// half of [`Board::neighbour_breakdown`]. Like there, the diagonal neighbours are counted even
+    /// if the board uses the von Neumann neighbourhood
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Coord};
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "_____",
+    ///     "_#_#_",
+    ///     "__#__",
+    ///     "_##__",
+    ///     "_____",
+    /// ].join("\n"), '#');
+    ///
+    /// assert_eq!(board.live_on_diagonals(&Coord::new(2, 2)), 3);
+    /// ```
+    pub fn live_on_diagonals(&self, coord: &Coord) -> u8 {
+        self.neighbour_breakdown(coord).1
+    }
+
+    /// Checks whether a cell has an odd number of live neighbours. Useful for parity based rules,
+    /// like the replicator rules
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Coord};
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "_____",
+    ///     "__#__",
+    ///     "_____",
+    ///     "_#_#_",
+    ///     "_____",
+    /// ].join("\n"), '#');
+    ///
+    /// // Three live neighbours
+    /// assert!(board.live_neighbours_parity(&Coord::new(2, 2)));
+    /// // Two live neighbours
+    /// assert!(!board.live_neighbours_parity(&Coord::new(2, 3)));
+    /// ```
+    pub fn live_neighbours_parity(&self, coord: &Coord) -> bool {
+        self.get_live_neighbours_of(coord) % 2 == 1
+    }
+
+    /// Calls a closure for every cell on the board with its coordinate, current state and count
+    /// of live neighbours. This is the primitive needed to implement custom stepping logic
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, CellState};
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "_____",
+    ///     "__#__",
+    ///     "__#__",
+    ///     "__#__",
+    ///     "_____",
+    /// ].join("\n"), '#');
+    ///
+    /// let mut births = 0;
+    /// board.for_each_cell_with_neighbours(|_, cell_state, live_neighbours| {
+    ///     if cell_state == &CellState::Dead && live_neighbours == 3 {
+    ///         births += 1;
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(births, 2);
+    /// ```
+    pub fn for_each_cell_with_neighbours<F: FnMut(Coord, &CellState, u8)>(&self, mut f: F) {
+        for coord in self.cell_coords() {
+            f(
+                coord.clone(),
+                self.get_cell_state(coord),
+                self.get_live_neighbours_of(coord),
+            );
+        }
+    }
+
+    /// Finds the tightest rectangle containing all live cells when the board wraps around its
+    /// edges. A pattern straddling an edge gets a small box which starts near the right or bottom
+    /// edge and wraps around, instead of a box spanning almost the entire board. Returns the top
+    /// left corner of the box together with its width and height, or `None` if there are no live
+    /// cells
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Coord};
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "______",
+    ///     "#____#",
+    ///     "#____#",
+    ///     "______",
+    /// ].join("\n"), '#');
+    ///
+    /// let (top_left, width, height) = board.toroidal_bounding_box().unwrap();
+    ///
+    /// assert_eq!(top_left, Coord::new(5, 1));
+    /// assert_eq!(width, 2);
+    /// assert_eq!(height, 2);
+    /// ```
+    pub fn toroidal_bounding_box(&self) -> Option<(Coord, usize, usize)> {
+        let mut occupied_columns = vec![false; self.width()];
+        let mut occupied_rows = vec![false; self.height()];
+
+        for coord in &self.cell_coords {
+            if self.get_cell_state(coord) == &CellState::Alive {
+                occupied_columns[coord.x] = true;
+                occupied_rows[coord.y] = true;
+            }
+        }
+
+        let (x, width) = Board::wrapped_span(&occupied_columns)?;
+        let (y, height) = Board::wrapped_span(&occupied_rows)?;
+
+        Some((Coord::new(x, y), width, height))
+    }
+
+    /// Copies the live part of the board into a new board the size of the toroidal bounding box,
+    /// so patterns straddling the edges come out whole. Returns the top left corner of the box on
+    /// this board together with the copy, or `None` if there are no live cells
+    pub(crate) fn toroidal_crop(&self) -> Option<(Coord, Board)> {
+        let (top_left, width, height) = self.toroidal_bounding_box()?;
+        let mut crop = Board::new(width, height);
+
+        for y in 0..height {
+            for x in 0..width {
+                let source = Coord::new(
+                    (top_left.x + x) % self.width(),
+                    (top_left.y + y) % self.height(),
+                );
+
+                if self.get_cell_state(&source) == &CellState::Alive {
+                    crop.revive_cell(&Coord::new(x, y));
+                }
+            }
+        }
+
+        Some((top_left, crop))
+    }
+
+    /// Groups the live cells into components of cells connected through their neighbours, following
+    /// the boundary and neighbourhood of the board
+    pub(crate) fn live_components(&self) -> Vec<Vec<Coord>> {
+        let mut visited = HashSet::new();
+        let mut components = Vec::new();
+
+        for start in &self.cell_coords {
+            if self.get_cell_state(start) != &CellState::Alive || visited.contains(start) {
+                continue;
+            }
+
+            visited.insert(start.clone());
+            let mut component = Vec::new();
+            let mut stack = vec![start.clone()];
+
+            while let Some(coord) = stack.pop() {
+                for neighbour in self.get_neighbour_coords(&coord) {
+                    if self.get_cell_state(&neighbour) == &CellState::Alive
+                        && visited.insert(neighbour.clone())
+                    {
+                        stack.push(neighbour);
+                    }
+                }
+                component.push(coord);
+            }
+
+            components.push(component);
+        }
+
+        components
+    }
+
+    /// Finds the shortest wrapped span covering all occupied indices by skipping the largest run of
+    /// unoccupied ones. Returns the start index and length of the span, or `None` if nothing is
+    /// occupied
+    fn wrapped_span(occupied: &[bool]) -> Option<(usize, usize)> {
+        let len = occupied.len();
+
+        if !occupied.contains(&true) {
+            return None;
+        }
+
+        // Walk twice around the axis so runs of unoccupied indices crossing the edge are seen whole
+        let mut largest_gap = (0, 0);
+        let mut gap_start: Option<usize> = None;
+        for i in 0..(2 * len) {
+            if occupied[i % len] {
+                if let Some(start) = gap_start.take() {
+                    let gap_len = (i - start).min(len);
+                    if gap_len > largest_gap.1 {
+                        largest_gap = (start, gap_len);
+                    }
+                }
+            } else if gap_start.is_none() {
+                gap_start = Some(i);
+            }
+        }
+
+        let (gap_start, gap_len) = largest_gap;
+
+        Some(((gap_start + gap_len) % len, len - gap_len))
+    }
+
+    /// Finds the largest rectangle containing only dead cells, for example to find a free spot to
+    /// place a new pattern. Returns the top left corner of the rectangle together with its width
+    /// and height. If all cells are alive, the width and height are 0
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Coord};
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "#####",
+    ///     "#___#",
+    ///     "#___#",
+    ///     "#####",
+    /// ].join("\n"), '#');
+    ///
+    /// let (top_left, width, height) = board.largest_empty_rectangle();
+    ///
+    /// assert_eq!(top_left, Coord::new(1, 1));
+    /// assert_eq!(width, 3);
+    /// assert_eq!(height, 2);
+    /// ```
+    pub fn largest_empty_rectangle(&self) -> (Coord, usize, usize) {
+        let mut best = (Coord::new(0, 0), 0, 0);
+        // Number of consecutive dead cells ending at the current row, for each column
+        let mut heights = vec![0; self.width()];
+
+        for (y, row) in self.cells.iter().enumerate() {
+            for (x, cell_state) in row.iter().enumerate() {
+                heights[x] = match cell_state {
+                    CellState::Alive => 0,
+                    CellState::Dead => heights[x] + 1,
+                };
+            }
+
+            // Find the largest rectangle in the histogram of heights, using a stack of column
+            // indices with increasing heights
+            let mut stack: Vec<usize> = Vec::new();
+            for x in 0..=heights.len() {
+                let height = heights.get(x).copied().unwrap_or(0);
+
+                while let Some(&top) = stack.last() {
+                    if heights[top] < height {
+                        break;
+                    }
+                    stack.pop();
+
+                    let left = stack.last().map_or(0, |&i| i + 1);
+                    let (width, height) = (x - left, heights[top]);
+
+                    if width * height > best.1 * best.2 {
+                        best = (Coord::new(left, y + 1 - height), width, height);
+                    }
+                }
+
+                stack.push(x);
+            }
+        }
+
+        best
+    }
+
+    /// Gets the coordinates of the cells along the edges of the board, going clockwise from the
+    /// top left corner: the top row, the right column, the bottom row and the left column. Each
+    /// corner is only included once
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Coord};
+    ///
+    /// let board = Board::new(3, 3);
+    ///
+    /// assert_eq!(board.perimeter_coords(), vec![
+    ///     Coord::new(0, 0),
+    ///     Coord::new(1, 0),
+    ///     Coord::new(2, 0),
+    ///     Coord::new(2, 1),
+    ///     Coord::new(2, 2),
+    ///     Coord::new(1, 2),
+    ///     Coord::new(0, 2),
+    ///     Coord::new(0, 1),
+    /// ]);
+    /// ```
+    pub fn perimeter_coords(&self) -> Vec<Coord> {
+        let (width, height) = (self.width(), self.height());
+        let mut coords = Vec::with_capacity(2 * (width + height));
+
+        coords.extend((0..width).map(|x| Coord::new(x, 0)));
+        coords.extend((1..height).map(|y| Coord::new(width - 1, y)));
+        if height > 1 {
+            coords.extend((0..width - 1).rev().map(|x| Coord::new(x, height - 1)));
+        }
+        if width > 1 {
+            coords.extend((1..height - 1).rev().map(|y| Coord::new(0, y)));
+        }
+
+        coords
+    }
+
+    /// Measures how disordered the board is, as the Shannon entropy in bits of the configurations
+    /// of `block_size` by `block_size` blocks tiling the board. Blocks which would extend past the
+    /// edges of the board are left out. A uniform board has an entropy of 0, while random boards
+    /// approach `block_size * block_size` bits
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::Board;
+    ///
+    /// let uniform = Board::new(8, 8);
+    /// let striped = Board::from_str(&vec![
+    ///     "#_#_",
+    ///     "#_#_",
+    /// ].join("\n"), '#');
+    ///
+    /// assert_eq!(uniform.block_entropy(2), 0.0);
+    /// assert_eq!(striped.block_entropy(1), 1.0);
+    /// ```
+    ///
+    /// # Panics
+    /// If the block size is 0
+    pub fn block_entropy(&self, block_size: usize) -> f64 {
+        if block_size == 0 {
+            panic!("Block size must be at least 1");
+        }
+
+        let mut configurations: HashMap<Vec<bool>, usize> = HashMap::new();
+
+        for top in (0..self.height() / block_size).map(|i| i * block_size) {
+            for left in (0..self.width() / block_size).map(|i| i * block_size) {
+                let configuration = self.cells[top..top + block_size]
+                    .iter()
+                    .flat_map(|row| &row[left..left + block_size])
+                    .map(|cell_state| cell_state == &CellState::Alive)
+                    .collect();
+
+                *configurations.entry(configuration).or_insert(0) += 1;
+            }
+        }
+
+        let blocks: usize = configurations.values().sum();
+
+        configurations
+            .values()
+            .map(|&count| {
+                let p = count as f64 / blocks as f64;
+                -p * p.log2()
+            })
+            .sum::<f64>()
+            // Avoid reporting -0.0 for uniform boards
+            .max(0.0)
+    }
+
+    /// Creates a copy of the board rotated 90 degrees clockwise, so the width and height are
+    /// swapped and the top row becomes the rightmost column. The copy has the same boundary and
+    /// neighbourhood, except that the twist of a Klein bottle moves to the other pair of edges
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::Board;
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "##_",
+    ///     "___",
+    /// ].join("\n"), '#');
+    ///
+    /// assert_eq!(board.rotate_cw().to_str('#', '_'), vec![
+    ///     "_#",
+    ///     "_#",
+    ///     "__",
+    /// ].join("\n"));
+    /// ```
+    pub fn rotate_cw(&self) -> Board {
+        let height = self.height();
+
+        let mut rotated =
+            self.transformed(height, self.width(), |x, y| Coord::new(y, height - 1 - x));
+        rotated.set_boundary(self.boundary.rotated());
+
+        rotated
+    }
+
+    /// Creates a copy of the board rotated 90 degrees counterclockwise, so the width and height are
+    /// swapped and the top row becomes the leftmost column. The copy has the same boundary and
+    /// neighbourhood, except that the twist of a Klein bottle moves to the other pair of edges
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::Board;
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "##_",
+    ///     "___",
+    /// ].join("\n"), '#');
+    ///
+    /// assert_eq!(board.rotate_ccw().to_str('#', '_'), vec![
+    ///     "__",
+    ///     "#_",
+    ///     "#_",
+    /// ].join("\n"));
+    /// ```
+    pub fn rotate_ccw(&self) -> Board {
+        let width = self.width();
+
+        let mut rotated =
+            self.transformed(self.height(), width, |x, y| Coord::new(width - 1 - y, x));
+        rotated.set_boundary(self.boundary.rotated());
+
+        rotated
+    }
+
+    /// Creates a copy of the board mirrored from left to right, so the columns come in reverse
+    /// order. The copy has the same size, boundary and neighbourhood
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::Board;
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "##_",
+    ///     "#__",
+    /// ].join("\n"), '#');
+    ///
+    /// assert_eq!(board.flip_horizontal().to_str('#', '_'), vec![
+    ///     "_##",
+    ///     "__#",
+    /// ].join("\n"));
+    /// ```
+    pub fn flip_horizontal(&self) -> Board {
+        let width = self.width();
+
+        self.transformed(width, self.height(), |x, y| Coord::new(width - 1 - x, y))
+    }
+
+    /// Creates a copy of the board mirrored from top to bottom, so the rows come in reverse order.
+    /// The copy has the same size, boundary and neighbourhood
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::Board;
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "##_",
+    ///     "#__",
+    /// ].join("\n"), '#');
+    ///
+    /// assert_eq!(board.flip_vertical().to_str('#', '_'), vec![
+    ///     "#__",
+    ///     "##_",
+    /// ].join("\n"));
+    /// ```
+    pub fn flip_vertical(&self) -> Board {
+        let height = self.height();
+
+        self.transformed(self.width(), height, |x, y| Coord::new(x, height - 1 - y))
+    }
+
+    /// Creates a board of the given size where each cell takes the state of the cell on this board
+    /// at the coordinate given by the closure, keeping the boundary and neighbourhood
+    fn transformed<F: Fn(usize, usize) -> Coord>(
+        &self,
+        width: usize,
+        height: usize,
+        f: F,
+    ) -> Board {
+        let mut board = Board::from_fn(width, height, |x, y| self.get_cell_state(&f(x, y)).clone());
+        board.set_boundary(self.boundary);
+        board.set_neighbourhood(self.neighbourhood);
+
+        board
+    }
+
+    /// Stamps a pattern onto the board with its top left corner at the given coordinate, combining
+    /// the cells of the pattern with the cells of the board as given by the mode. Cells of the
+    /// pattern landing outside the board are left out. On a toroidal board, a coordinate past the
+    /// edges is wrapped onto the board first, so stamping at `(width + 2, 0)` is the same as
+    /// stamping at `(2, 0)`. Boards with other boundaries leave the coordinate as it is
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Coord, StampMode, patterns};
+    ///
+    /// let mut board = Board::new(6, 5);
+    /// board.stamp(&patterns::glider(), &Coord::new(1, 1), StampMode::Or);
+    ///
+    /// assert_eq!(board.to_str('#', '_'), vec![
+    ///     "______",
+    ///     "__#___",
+    ///     "___#__",
+    ///     "_###__",
+    ///     "______",
+    /// ].join("\n"));
+    ///
+    /// // Stamping the same glider again with xor removes it
+    /// board.stamp(&patterns::glider(), &Coord::new(1, 1), StampMode::Xor);
+    ///
+    /// assert!(board.is_empty());
+    /// ```
+    pub fn stamp(&mut self, pattern: &Board, at: &Coord, mode: StampMode) {
+        let at = self.stamp_origin(at);
+
+        for coord in pattern.cell_coords() {
+            let target = match (at.x.checked_add(coord.x), at.y.checked_add(coord.y)) {
+                (Some(x), Some(y)) if x < self.width() && y < self.height() => Coord::new(x, y),
+                _ => continue,
+            };
+
+            let alive = pattern.get_cell_state(coord) == &CellState::Alive;
+            match (mode, alive) {
+                (StampMode::Or, true) | (StampMode::Copy, true) => self.revive_cell(&target),
+                (StampMode::Copy, false) => self.kill_cell(&target),
+                (StampMode::Xor, true) => self.toggle_cell(&target),
+                (StampMode::Or, false) | (StampMode::Xor, false) => (),
+            }
+        }
+    }
+
+    /// Wraps the top left corner of something stamped onto a toroidal board onto the board. Other
+    /// boundaries keep the coordinate, so anything past the edges is clipped
+    fn stamp_origin(&self, at: &Coord) -> Coord {
+        match self.boundary {
+            Boundary::Toroidal => Coord::new(at.x % self.width(), at.y % self.height()),
+            _ => at.clone(),
+        }
+    }
+
+    /// Writes text onto the board using a bitmap font, reviving the cells of the live pixels of
+    /// each glyph. The top left corner of the text is placed at the given coordinate, there is
+    /// one column of space between glyphs, and newlines start a new line of text below. Characters
+    /// missing from the font are left blank, and pixels falling outside the board are left out.
+    /// The coordinate is wrapped onto toroidal boards like with [`Board::stamp`]
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Coord, Font};
+    ///
+    /// let mut board = Board::new(9, 7);
+    /// board.stamp_text("HI", Coord::new(1, 1), &Font::default());
+    ///
+    /// assert_eq!(board.to_str('#', '_'), vec![
+    ///     "_________",
+    ///     "_#_#_###_",
+    ///     "_#_#__#__",
+    ///     "_###__#__",
+    ///     "_#_#__#__",
+    ///     "_#_#_###_",
+    ///     "_________",
+    /// ].join("\n"));
+    /// ```
+    pub fn stamp_text(&mut self, text: &str, at: Coord, font: &Font) {
+        let at = self.stamp_origin(&at);
+
+        // Offsets past the end of `usize` are off the board, so they are clipped like any other
+        let offset = |start: usize, index: usize, size: usize| {
+            index
+                .checked_mul(size + 1)
+                .and_then(|offset| start.checked_add(offset))
+        };
+
+        for (line_index, line) in text.lines().enumerate() {
+            let Some(top) = offset(at.y, line_index, font.height()) else {
+                break;
+            };
+
+            for (char_index, c) in line.chars().enumerate() {
+                let Some(left) = offset(at.x, char_index, font.width()) else {
+                    break;
+                };
+
+                let Some(glyph) = font.glyph(c) else {
+                    continue;
+                };
+
+                for (y, row) in glyph.iter().enumerate() {
+                    for (x, &pixel) in row.iter().enumerate() {
+                        let (Some(x), Some(y)) = (left.checked_add(x), top.checked_add(y)) else {
+                            continue;
+                        };
+
+                        if pixel && x < self.width() && y < self.height() {
+                            self.revive_cell(&Coord::new(x, y));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Creates a copy of the board advanced one generation under the given rules, leaving this
+    /// board untouched
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, rules};
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "_____",
+    ///     "__#__",
+    ///     "__#__",
+    ///     "__#__",
+    ///     "_____",
+    /// ].join("\n"), '#');
+    ///
+    /// let next = board.advanced(&rules::conways());
+    ///
+    /// assert_eq!(next.to_str('#', '_'), vec![
+    ///     "_____",
+    ///     "_____",
+    ///     "_###_",
+    ///     "_____",
+    ///     "_____",
+    /// ].join("\n"));
+    /// ```
+    pub fn advanced(&self, rules: &Rules) -> Board {
+        let mut game = Game::new(self.clone(), rules.clone());
+
+        game.advance_to_next_gen();
+
+        game.board().clone()
+    }
+
+    /// Lists every cell which differs from the given baseline board, together with its current
+    /// state. This is all that is needed to bring a copy of the baseline up to date with this
+    /// board, however many generations apart they are
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Coord, CellState};
+    ///
+    /// let baseline = Board::from_str(&vec![
+    ///     "#_",
+    ///     "__",
+    /// ].join("\n"), '#');
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "__",
+    ///     "_#",
+    /// ].join("\n"), '#');
+    ///
+    /// assert_eq!(board.changes_since(&baseline), vec![
+    ///     (Coord::new(0, 0), CellState::Dead),
+    ///     (Coord::new(1, 1), CellState::Alive),
+    /// ]);
+    /// ```
+    ///
+    /// # Panics
+    /// If the boards have different dimensions
+    pub fn changes_since(&self, baseline: &Board) -> Vec<(Coord, CellState)> {
+        if self.width() != baseline.width() || self.height() != baseline.height() {
+            panic!("The boards must have the same dimensions");
+        }
+
+        self.cell_coords
+            .iter()
+            .filter(|c| self.get_cell_state(c) != baseline.get_cell_state(c))
+            .map(|c| (c.clone(), self.get_cell_state(c).clone()))
+            .collect()
+    }
+
+    /// Labels what happened to each cell between this board and the next generation, row by row.
+    /// This is like [`Board::changes_since`], but also tells apart the cells which did not change
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, CellTransition, rules};
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "_____",
+    ///     "_____",
+    ///     "_###_",
+    ///     "_____",
+    ///     "_____",
+    /// ].join("\n"), '#');
+    /// let next = board.advanced(&rules::conways());
+    ///
+    /// let transitions = board.classify_transition(&next);
+    ///
+    /// assert_eq!(transitions[1][2], CellTransition::Born);
+    /// assert_eq!(transitions[2][1], CellTransition::Died);
+    /// assert_eq!(transitions[2][2], CellTransition::Survived);
+    /// assert_eq!(transitions[0][0], CellTransition::StayedDead);
+    /// ```
+    ///
+    /// # Panics
+    /// If the boards have different dimensions
+    pub fn classify_transition(&self, next: &Board) -> Vec<Vec<CellTransition>> {
+        if self.width() != next.width() || self.height() != next.height() {
+            panic!("The boards must have the same dimensions");
+        }
+
+        self.cells
+            .iter()
+            .zip(&next.cells)
+            .map(|(row, next_row)| {
+                row.iter()
+                    .zip(next_row)
+                    .map(|states| match states {
+                        (CellState::Dead, CellState::Alive) => CellTransition::Born,
+                        (CellState::Alive, CellState::Alive) => CellTransition::Survived,
+                        (CellState::Alive, CellState::Dead) => CellTransition::Died,
+                        (CellState::Dead, CellState::Dead) => CellTransition::StayedDead,
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Counts how many cells there are of each combination of cell state and number of live
+    /// neighbours. Together with a set of rules, this tells how many cells will be born, survive
+    /// or die in the next generation
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, CellState};
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "_____",
+    ///     "__#__",
+    ///     "__#__",
+    ///     "__#__",
+    ///     "_____",
+    /// ].join("\n"), '#');
+    ///
+    /// let census = board.configuration_census();
+    ///
+    /// // The ends of the blinker have one live neighbour, and the middle has two
+    /// assert_eq!(census[&(CellState::Alive, 1)], 2);
+    /// assert_eq!(census[&(CellState::Alive, 2)], 1);
+    /// // The cells on either side of the middle will be born
+    /// assert_eq!(census[&(CellState::Dead, 3)], 2);
+    /// ```
+    pub fn configuration_census(&self) -> HashMap<(CellState, u8), usize> {
+        let mut census = HashMap::new();
+
+        for coord in &self.cell_coords {
+            let configuration = (
+                self.get_cell_state(coord).clone(),
+                self.get_live_neighbours_of(coord),
+            );
+            *census.entry(configuration).or_insert(0) += 1;
+        }
+
+        census
+    }
+
+    /// Measures how symmetric the live cells are, as the fraction of the cells within their
+    /// bounding box which have the same state as their mirror images. With four-fold symmetry, a
+    /// cell must agree with all three of its mirror images. A perfectly symmetric pattern, or an
+    /// empty board, scores 1
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, SymmetryKind};
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "_____",
+    ///     "_#_#_",
+    ///     "__#__",
+    ///     "_____",
+    /// ].join("\n"), '#');
+    ///
+    /// assert_eq!(board.symmetry_score(SymmetryKind::Horizontal), 1.0);
+    /// // Flipped upside down, every cell in the bounding box changes
+    /// assert_eq!(board.symmetry_score(SymmetryKind::Vertical), 0.0);
+    /// ```
+    pub fn symmetry_score(&self, kind: SymmetryKind) -> f64 {
+        let (min, max) = match self.live_bounds() {
+            Some(extent) => extent,
+            None => return 1.0,
+        };
+
+        // Cells outside the bounding box are dead, as it contains all live cells
+        let state_at = |x: usize, y: usize| {
+            if x >= min.x && x <= max.x && y >= min.y && y <= max.y {
+                self.get_cell_state(&Coord::new(x, y))
+            } else {
+                &CellState::Dead
+            }
+        };
+
+        let mut cells = 0;
+        let mut agreeing = 0;
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                let mirrored_x = min.x + max.x - x;
+                let mirrored_y = min.y + max.y - y;
+
+                let reflections = match kind {
+                    SymmetryKind::Horizontal => vec![(mirrored_x, y)],
+                    SymmetryKind::Vertical => vec![(x, mirrored_y)],
+                    SymmetryKind::Diagonal => vec![(min.x + y - min.y, min.y + x - min.x)],
+                    SymmetryKind::FourFold => {
+                        vec![(mirrored_x, y), (x, mirrored_y), (mirrored_x, mirrored_y)]
+                    }
+                };
+
+                let state = state_at(x, y);
+                cells += 1;
+                if reflections
+                    .into_iter()
+                    .all(|(x, y)| state_at(x, y) == state)
+                {
+                    agreeing += 1;
+                }
+            }
+        }
+
+        agreeing as f64 / cells as f64
+    }
+
+    /// Makes the board symmetric by reviving the mirror image of every live cell. Cells are only
+    /// ever revived, so a rough sketch of one side of a pattern is completed by its reflection
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, SymmetryKind};
+    ///
+    /// let mut board = Board::from_str(&vec![
+    ///     "##___",
+    ///     "#____",
+    ///     "_____",
+    ///     "_____",
+    /// ].join("\n"), '#');
+    ///
+    /// board.symmetrize(SymmetryKind::FourFold);
+    ///
+    /// assert_eq!(board.to_str('#', '_'), vec![
+    ///     "##_##",
+    ///     "#___#",
+    ///     "#___#",
+    ///     "##_##",
+    /// ].join("\n"));
+    /// ```
+    ///
+    /// # Panics
+    /// If the symmetry is diagonal and the board is not square
+    pub fn symmetrize(&mut self, symmetry: SymmetryKind) {
+        if symmetry == SymmetryKind::Diagonal && self.width() != self.height() {
+            panic!("Diagonal symmetry needs a square board");
+        }
+
+        let max_x = self.width() - 1;
+        let max_y = self.height() - 1;
+
+        let reflect = |c: &Coord| -> Vec<Coord> {
+            match symmetry {
+                SymmetryKind::Horizontal => vec![Coord::new(max_x - c.x, c.y)],
+                SymmetryKind::Vertical => vec![Coord::new(c.x, max_y - c.y)],
+                SymmetryKind::Diagonal => vec![Coord::new(c.y, c.x)],
+                SymmetryKind::FourFold => vec![
+                    Coord::new(max_x - c.x, c.y),
+                    Coord::new(c.x, max_y - c.y),
+                    Coord::new(max_x - c.x, max_y - c.y),
+                ],
+            }
+        };
+
+        let reflections: Vec<Coord> = self.live_cells().flat_map(reflect).collect();
+
+        for coord in &reflections {
+            self.revive_cell(coord);
+        }
+    }
+
+    /// Finds the convex hull of the live cells, using the monotone chain algorithm. The corners
+    /// of the hull are returned clockwise as the board is printed, starting with the leftmost live
+    /// cell (the topmost one if there are several). Cells lying on an edge of the hull between two
+    /// corners are not included. With fewer than three live cells, or if they are all on a line,
+    /// the hull is just the live cells at the ends
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Coord};
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "_#__",
+    ///     "#_#_",
+    ///     "_##_",
+    /// ].join("\n"), '#');
+    ///
+    /// assert_eq!(board.live_convex_hull(), vec![
+    ///     Coord::new(0, 1),
+    ///     Coord::new(1, 0),
+    ///     Coord::new(2, 1),
+    ///     Coord::new(2, 2),
+    ///     Coord::new(1, 2),
+    /// ]);
+    /// ```
+    pub fn live_convex_hull(&self) -> Vec<Coord> {
+        let mut points: Vec<&Coord> = self.live_cells().collect();
+        points.sort_by_key(|c| (c.x, c.y));
+
+        if points.len() < 3 {
+            return points.into_iter().cloned().collect();
+        }
+
+        // Positive if going from o to a to b turns clockwise as the board is printed
+        let cross = |o: &Coord, a: &Coord, b: &Coord| {
+            let (ox, oy) = (o.x as i64, o.y as i64);
+            (a.x as i64 - ox) * (b.y as i64 - oy) - (a.y as i64 - oy) * (b.x as i64 - ox)
+        };
+
+        let mut hull: Vec<&Coord> = Vec::with_capacity(points.len() * 2);
+
+        // Build the top half from left to right, then the bottom half from right to left
+        for pass in [points.clone(), points.iter().rev().cloned().collect()] {
+            let start = hull.len();
+            for point in pass {
+                while hull.len() >= start + 2
+                    && cross(hull[hull.len() - 2], hull[hull.len() - 1], point) <= 0
+                {
+                    hull.pop();
+                }
+                hull.push(point);
+            }
+            // The last point of each half is the first point of the other
+            hull.pop();
+        }
+
+        hull.into_iter().cloned().collect()
+    }
+
+    /// Changes the size of the board, keeping the states of the cells inside both the old and the
+    /// new size. Cells added by growing the board are dead, and cells outside the new size are
+    /// dropped. The board keeps its boundary and neighbourhood
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::Board;
+    ///
+    /// let mut board = Board::from_str(&vec![
+    ///     "_#",
+    ///     "##",
+    /// ].join("\n"), '#');
+    ///
+    /// board.resize(3, 3);
+    ///
+    /// assert_eq!(board.to_str('#', '_'), vec![
+    ///     "_#_",
+    ///     "##_",
+    ///     "___",
+    /// ].join("\n"));
+    /// ```
+    ///
+    /// # Panics
+    /// If the new width or height is 0
+    pub fn resize(&mut self, new_width: usize, new_height: usize) {
+        let mut resized = Board::new(new_width, new_height);
+        resized.set_boundary(self.boundary);
+        resized.set_neighbourhood(self.neighbourhood);
+
+        for coord in self.live_cells() {
+            resized.try_revive_cell(coord);
+        }
+
+        *self = resized;
+    }
+
+    /// Kills every cell on the board. The board keeps its size and allocations, so it can be reused
+    /// instead of creating a new one
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::Board;
+    ///
+    /// let mut board = Board::from_str(&vec![
+    ///     "#_",
+    ///     "##",
+    /// ].join("\n"), '#');
+    ///
+    /// board.clear();
+    ///
+    /// assert!(board.is_empty());
+    /// assert_eq!(board.width(), 2);
+    /// ```
+    pub fn clear(&mut self) {
+        for row in &mut self.cells {
+            row.fill(CellState::Dead);
+        }
+        self.live_count = Some(0);
+    }
+
+    /// Sets every cell in a row to the given state
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, CellState};
+    ///
+    /// let mut board = Board::new(4, 3);
+    /// board.set_row(1, CellState::Alive);
+    ///
+    /// assert_eq!(board.to_str('#', '_'), vec![
+    ///     "____",
+    ///     "####",
+    ///     "____",
+    /// ].join("\n"));
+    /// ```
+    ///
+    /// # Panics
+    /// If the row is outside the board
+    pub fn set_row(&mut self, y: usize, state: CellState) {
+        assert!(y < self.height(), "Row {} is outside the board", y);
+
+        for x in 0..self.width() {
+            self.set_cell_state(&Coord::new(x, y), state.clone());
+        }
+    }
+
+    /// Sets every cell in a column to the given state
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, CellState};
+    ///
+    /// let mut board = Board::new(4, 3);
+    /// board.set_column(2, CellState::Alive);
+    ///
+    /// assert_eq!(board.to_str('#', '_'), vec![
+    ///     "__#_",
+    ///     "__#_",
+    ///     "__#_",
+    /// ].join("\n"));
+    /// ```
+    ///
+    /// # Panics
+    /// If the column is outside the board
+    pub fn set_column(&mut self, x: usize, state: CellState) {
+        assert!(x < self.width(), "Column {} is outside the board", x);
+
+        for y in 0..self.height() {
+            self.set_cell_state(&Coord::new(x, y), state.clone());
+        }
+    }
+
+    /// Copies the rectangle with the given top left corner and size into a new board. Parts of the
+    /// rectangle outside this board are dead on the new board
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Coord};
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "________",
+    ///     "___#____",
+    ///     "____#___",
+    ///     "__###___",
+    ///     "________",
+    /// ].join("\n"), '#');
+    ///
+    /// let (min, max) = board.live_bounds().unwrap();
+    /// let glider = board.subboard(&min, max.x - min.x + 1, max.y - min.y + 1);
+    ///
+    /// assert_eq!(glider, Board::from_str(&vec![
+    ///     "_#_",
+    ///     "__#",
+    ///     "###",
+    /// ].join("\n"), '#'));
+    /// ```
+    ///
+    /// # Panics
+    /// If width or height is 0
+    pub fn subboard(&self, top_left: &Coord, width: usize, height: usize) -> Board {
+        Board::from_fn(width, height, |x, y| {
+            match (top_left.x.checked_add(x), top_left.y.checked_add(y)) {
+                (Some(x), Some(y)) => self
+                    .try_get_cell_state(&Coord::new(x, y))
+                    .cloned()
+                    .unwrap_or(CellState::Dead),
+                _ => CellState::Dead,
+            }
+        })
+    }
+
+    /// Kills every cell in the rectangle with the given top left corner and size. Parts of the
+    /// rectangle outside the board are ignored, and cells outside the rectangle are left alone
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Coord};
+    ///
+    /// let mut board = Board::from_str(&vec![
+    ///     "####",
+    ///     "####",
+    ///     "####",
+    /// ].join("\n"), '#');
+    ///
+    /// board.clear_region(Coord::new(2, 1), 5, 5);
+    ///
+    /// assert_eq!(board.to_str('#', '_'), vec![
+    ///     "####",
+    ///     "##__",
+    ///     "##__",
+    /// ].join("\n"));
+    /// ```
+    pub fn clear_region(&mut self, top_left: Coord, width: usize, height: usize) {
+        let right = top_left.x.saturating_add(width).min(self.width());
+        let bottom = top_left.y.saturating_add(height).min(self.height());
+
+        for y in top_left.y..bottom {
+            for x in top_left.x..right {
+                self.kill_cell(&Coord::new(x, y));
+            }
+        }
+    }
+}
+
+impl fmt::Display for Board {
+    /// Draws the board with `#` for live cells and spaces for dead cells
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::Board;
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "#_",
+    ///     "_#",
+    /// ].join("\n"), '#');
+    ///
+    /// assert_eq!(board.to_string(), "# \n #");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str('#', ' '))
+    }
+}
+
+impl Index<Coord> for Board {
+    type Output = CellState;
+
+    /// Gets the state of the cell at the given coordinate, like [`Board::get_cell_state`]
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Coord, CellState};
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "#_",
+    ///     "_#",
+    /// ].join("\n"), '#');
+    ///
+    /// assert_eq!(board[Coord::new(1, 1)], CellState::Alive);
+    /// ```
+    fn index(&self, coord: Coord) -> &CellState {
+        self.get_cell_state(&coord)
+    }
+}
+
+impl IndexMut<Coord> for Board {
+    /// Gets a mutable reference to the state of the cell at the given coordinate. As the change
+    /// cannot be seen by the board, the live cells are counted again the next time they are needed
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, Coord, CellState};
+    ///
+    /// let mut board = Board::new(2, 2);
+    /// board[Coord::new(0, 1)] = CellState::Alive;
+    ///
+    /// assert_eq!(board.get_cell_state(&Coord::new(0, 1)), &CellState::Alive);
+    /// ```
+    fn index_mut(&mut self, coord: Coord) -> &mut CellState {
+        self.get_cell_state_mut(&coord)
+    }
+}
+
+#[cfg(test)]
+// Some of the original tests build their rows with `vec!`
+#[allow(clippy::useless_vec)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[should_panic]
+    fn creating_board_with_0_width_panics() {
+        Board::new(0, 10);
+    }
+
+    #[test]
+    #[should_panic]
+    fn creating_board_with_0_height_panics() {
+        Board::new(10, 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn creating_board_with_0_cells_panics() {
+        Board::new(0, 0);
+    }
+
+    #[test]
+    fn all_cells_initially_dead() {
+        let height = 10;
+        let width = 5;
+        let board = Board::new(width, height);
+
+        println!("{:?}", board.cell_coords());
+
+        let all_dead = board
+            .cell_coords()
+            .iter()
+            .map(|c| board.get_cell_state(c))
+            .all(|s| s == &CellState::Dead);
+
+        assert!(all_dead);
+    }
+
+    #[test]
+    #[should_panic]
+    fn creating_board_from_grid_with_0_cells_panics() {
+        Board::from_grid(&Vec::new());
+    }
+
+    #[test]
+    fn creating_board_from_grid_gets_correct_dimensions() {
+        let grid = vec![vec![CellState::Alive, CellState::Dead]];
+
+        let board = Board::from_grid(&grid);
+
+        assert_eq!(board.width(), 2);
+        assert_eq!(board.height(), 1);
+    }
+
+    #[test]
+    fn cearing_board_from_grid_with_uneven_rows_works() {
+        let grid = vec![
+            vec![],
+            vec![CellState::Dead, CellState::Alive],
+            vec![CellState::Dead],
+        ];
+
+        let board = Board::from_grid(&grid);
+
+        assert_eq!(board.width(), 2);
+        assert_eq!(board.height(), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn creating_board_from_empty_string_panics() {
+        Board::from_str("", '#');
+    }
+
+    #[test]
+    #[should_panic]
+    fn creating_board_from_string_with_only_newlines_panics() {
+        Board::from_str("\n\n\n\n\n", '#');
+    }
+
+    #[test]
+    #[should_panic]
+    fn creating_board_from_string_with_only_carriage_returns_panics() {
+        Board::from_str("\r\r\r\r", '#');
+    }
+
+    #[test]
+    #[should_panic]
+    fn creating_board_from_string_with_newline_as_alive_char_panics() {
+        Board::from_str("#", '\n');
+    }
+
+    #[test]
+    #[should_panic]
+    fn creating_board_from_string_with_carriage_return_as_alive_char_panics() {
+        Board::from_str("#", '\r');
+    }
+
+    #[test]
+    fn creating_board_from_string_ignores_carriage_returns() {
+        let board = Board::from_str(&vec!["##", "##"].join("\n"), '#');
+
+        assert_eq!(board.width(), 2);
+        assert_eq!(board.height(), 2);
+    }
+
+    #[test]
+    fn creating_board_from_string_trailing_newline() {
+        // Trailing newline causes a bottom row with dead cells
+        let board = Board::from_str("##\n##\n", '#');
+
+        assert_eq!(board.width(), 2);
+        assert_eq!(board.height(), 3);
+    }
+
+    #[test]
+    fn creating_board_from_string_counts_non_live_characters_as_dead() {
+        let board = Board::from_str(&vec!["*T#5.", " #_#?"].join("\n"), '#');
+
+        assert_eq!(board.get_cell_state(&Coord::new(0, 0)), &CellState::Dead);
+        assert_eq!(board.get_cell_state(&Coord::new(1, 0)), &CellState::Dead);
+        assert_eq!(board.get_cell_state(&Coord::new(2, 0)), &CellState::Alive);
+        assert_eq!(board.get_cell_state(&Coord::new(3, 0)), &CellState::Dead);
+        assert_eq!(board.get_cell_state(&Coord::new(4, 0)), &CellState::Dead);
+        assert_eq!(board.get_cell_state(&Coord::new(0, 1)), &CellState::Dead);
+        assert_eq!(board.get_cell_state(&Coord::new(1, 1)), &CellState::Alive);
+        assert_eq!(board.get_cell_state(&Coord::new(2, 1)), &CellState::Dead);
+        assert_eq!(board.get_cell_state(&Coord::new(3, 1)), &CellState::Alive);
+        assert_eq!(board.get_cell_state(&Coord::new(4, 1)), &CellState::Dead);
+    }
+
+    #[test]
+    #[should_panic]
+    fn to_str_panics_if_alive_char_is_newline() {
+        let board = Board::new(1, 1);
+
+        board.to_str('\n', ' ');
+    }
+
+    #[test]
+    #[should_panic]
+    fn to_str_panics_if_alive_char_is_carriage_return() {
+        let board = Board::new(1, 1);
+
+        board.to_str('\r', ' ');
+    }
+
+    #[test]
+    #[should_panic]
+    fn to_str_panics_if_dead_char_is_newline() {
+        let board = Board::new(1, 1);
+
+        board.to_str('#', '\n');
+    }
+
+    #[test]
+    #[should_panic]
+    fn to_str_panics_if_dead_char_is_carriage_return() {
+        let board = Board::new(1, 1);
+
+        board.to_str('#', '\r');
+    }
+
+    #[test]
+    fn gets_and_sets_state_of_cell() {
+        let height = 10;
+        let width = 5;
+        let mut board = Board::new(width, height);
+        let coord = Coord::new(0, 0);
+
+        assert_eq!(board.get_cell_state(&coord), &CellState::Dead);
+
+        board.revive_cell(&coord);
+
+        assert_eq!(board.get_cell_state(&coord), &CellState::Alive);
+
+        board.kill_cell(&coord);
+
+        assert_eq!(board.get_cell_state(&coord), &CellState::Dead);
+
+        board.toggle_cell(&coord);
+
+        assert_eq!(board.get_cell_state(&coord), &CellState::Alive);
+
+        board.toggle_cell(&coord);
+
+        assert_eq!(board.get_cell_state(&coord), &CellState::Dead);
+    }
+
+    #[test]
+    fn neighbours_should_be_correct_in_middle_of_board() {
+        let height = 10;
+        let width = 5;
+        let board = Board::new(width, height);
+        let coord = Coord::new(1, 1);
+
+        let expected_neighbours = vec![
+            Coord::new(0, 1),
+            Coord::new(2, 1),
+            Coord::new(1, 0),
+            Coord::new(1, 2),
+            Coord::new(0, 0),
+            Coord::new(0, 2),
+            Coord::new(2, 0),
+            Coord::new(2, 2),
+        ];
+
+        let neighbours = board.get_neighbour_coords(&coord);
+
+        for expected in expected_neighbours {
+            assert!(neighbours.contains(&expected));
+        }
+    }
+
+    #[test]
+    fn neighbours_should_be_correct_upper_left() {
+        let height = 10;
+        let width = 5;
+        let board = Board::new(width, height);
+        let coord = Coord::new(0, 0);
+
+        let expected_neighbours = vec![
+            Coord::new(width - 1, 0),
+            Coord::new(1, 0),
+            Coord::new(0, height - 1),
+            Coord::new(0, 1),
+            Coord::new(width - 1, height - 1),
+            Coord::new(width - 1, 1),
+            Coord::new(1, height - 1),
+            Coord::new(1, 1),
+        ];
+
+        let neighbours = board.get_neighbour_coords(&coord);
+
+        for expected in expected_neighbours {
+            assert!(neighbours.contains(&expected));
+        }
+    }
+
+    #[test]
+    fn neighbours_should_be_correct_lower_right() {
+        let height = 10;
+        let width = 5;
+        let board = Board::new(width, height);
+        let coord = Coord::new(width - 1, height - 1);
+
+        let expected_neighbours = vec![
+            Coord::new(0, 0),
+            Coord::new(0, height - 1),
+            Coord::new(0, height - 2),
+            Coord::new(width - 1, 0),
+            Coord::new(width - 2, 0),
+            Coord::new(width - 2, height - 2),
+            Coord::new(width - 1, height - 2),
+            Coord::new(width - 2, height - 1),
+        ];
+
+        let neighbours = board.get_neighbour_coords(&coord);
+
+        println!("{:?}", neighbours);
+        println!("{:?}", expected_neighbours);
+
+        for expected in expected_neighbours {
+            assert!(neighbours.contains(&expected));
+        }
+    }
+
+    #[test]
+    fn three_live_neighbours() {
+        let board = Board::from_str(
+            &vec!["_____", "_##__", "_____", "__#__", "_____"].join("\n"),
+            '#',
+        );
+
+        let coord = Coord::new(2, 2);
+
+        assert_eq!(board.get_live_neighbours_of(&coord), 3);
+    }
+
+    #[test]
+    fn for_each_cell_with_neighbours_visits_every_cell() {
+        // Every live cell is counted once by each of its 8 neighbours
+        let board = Board::from_str(&["#__", "_#_", "___"].join("\n"), '#');
+
+        let mut visited = 0;
+        let mut total_neighbours = 0;
+        board.for_each_cell_with_neighbours(|_, _, live_neighbours| {
+            visited += 1;
+            total_neighbours += live_neighbours as usize;
+        });
+
+        assert_eq!(visited, 9);
+        assert_eq!(total_neighbours, 16);
+    }
+
+    #[test]
+    fn to_rle_named_includes_comments_and_trims_to_live_cells() {
+        let board = Board::from_str(
+            &["______", "______", "__##__", "__#___", "______"].join("\n"),
+            '#',
+        );
+
+        let rle = board.to_rle_named("Partial block", &crate::rules::conways());
+        let lines: Vec<&str> = rle.lines().collect();
+
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], "#N Partial block");
+        assert!(lines[1].starts_with("#O "));
+        assert_eq!(lines[2], "x = 2, y = 2, rule = B3/S23");
+        assert_eq!(lines[3], "2o$o!");
+    }
+
+    #[test]
+    fn to_rle_named_on_empty_board_round_trips_as_single_dead_cell() {
+        let board = Board::new(5, 5);
+
+        let rle = board.to_rle_named("Nothing", &crate::rules::conways());
+
+        assert_eq!(Board::from_rle(&rle), Ok(Board::new(1, 1)));
+    }
+
+    #[test]
+    fn glider_stream_has_five_live_cells_per_glider() {
+        let count = 4;
+        let board = Board::with_glider_stream(30, 30, count, 6);
+
+        assert_eq!(board.population(), count * 5);
+    }
+
+    #[test]
+    fn glider_stream_places_gliders_along_diagonal() {
+        let board = Board::with_glider_stream(10, 10, 2, 4);
+
+        // Tail cells of the two gliders
+        assert_eq!(board.get_cell_state(&Coord::new(1, 0)), &CellState::Alive);
+        assert_eq!(board.get_cell_state(&Coord::new(5, 4)), &CellState::Alive);
+    }
+
+    #[test]
+    fn csv_round_trips() {
+        let board = Board::from_str(&["#__#", "_##_", "#___"].join("\n"), '#');
+
+        let parsed = Board::from_csv(&board.to_csv()).unwrap();
+
+        assert_eq!(parsed, board);
+    }
+
+    #[test]
+    fn from_csv_rejects_ragged_rows() {
+        assert_eq!(
+            Board::from_csv("1,0,1\n0,1"),
+            Err(ParseError::RaggedRow {
+                row: 1,
+                expected: 3,
+                found: 2
+            })
+        );
+    }
+
+    #[test]
+    fn from_csv_rejects_invalid_tokens() {
+        assert_eq!(
+            Board::from_csv("1,0\n0,x"),
+            Err(ParseError::InvalidToken {
+                row: 1,
+                column: 1,
+                token: "x".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn from_csv_rejects_empty_text() {
+        assert_eq!(Board::from_csv(""), Err(ParseError::Empty));
+    }
+
+    #[test]
+    fn toroidal_bounding_box_of_block_split_across_seam() {
+        let board = Board::from_str(&["#___#", "#___#", "_____"].join("\n"), '#');
+
+        let (top_left, width, height) = board.toroidal_bounding_box().unwrap();
+
+        assert_eq!(top_left, Coord::new(4, 0));
+        assert_eq!(width, 2);
+        assert_eq!(height, 2);
+    }
+
+    #[test]
+    fn toroidal_bounding_box_of_unsplit_pattern_is_ordinary_box() {
+        let board = Board::from_str(&["_____", "_##__", "__#__", "_____"].join("\n"), '#');
+
+        let (top_left, width, height) = board.toroidal_bounding_box().unwrap();
+
+        assert_eq!(top_left, Coord::new(1, 1));
+        assert_eq!(width, 2);
+        assert_eq!(height, 2);
+    }
+
+    #[test]
+    fn toroidal_bounding_box_of_full_row_spans_board() {
+        let board = Board::from_str(&["____", "####", "____"].join("\n"), '#');
+
+        let (top_left, width, height) = board.toroidal_bounding_box().unwrap();
+
+        assert_eq!(top_left, Coord::new(0, 1));
+        assert_eq!(width, 4);
+        assert_eq!(height, 1);
+    }
+
+    #[test]
+    fn toroidal_bounding_box_of_empty_board_is_none() {
+        assert_eq!(Board::new(3, 3).toroidal_bounding_box(), None);
+    }
+
+    #[test]
+    fn live_neighbours_parity_agrees_with_neighbour_count() {
+        let board = Board::from_str(&["#_#_", "_##_", "#__#", "_#__"].join("\n"), '#');
+
+        for coord in board.cell_coords() {
+            assert_eq!(
+                board.live_neighbours_parity(coord),
+                board.get_live_neighbours_of(coord) % 2 == 1
+            );
+        }
+    }
+
+    #[test]
+    fn creating_board_from_string_with_comments_skips_comment_lines() {
+        let board = Board::from_str_with_comments(
+            &["# Blinker", "# Period 2", "_O_", "_O_", "_O_"].join("\n"),
+            'O',
+            "#",
+        );
+
+        assert_eq!(
+            board,
+            Board::from_str(&["_#_", "_#_", "_#_"].join("\n"), '#')
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "must not be empty")]
+    fn creating_board_from_string_with_empty_comment_prefix_panics() {
+        Board::from_str_with_comments("_#\n#_", '#', "");
+    }
+
+    #[test]
+    #[should_panic(expected = "must not start with the alive character")]
+    fn creating_board_from_string_with_comment_prefix_like_live_cell_panics() {
+        Board::from_str_with_comments("# Glider\n#_\n_#", '#', "#");
+    }
+
+    #[test]
+    #[should_panic]
+    fn creating_board_from_string_with_only_comments_panics() {
+        Board::from_str_with_comments("// nothing\n// here", '#', "//");
+    }
+
+    #[test]
+    fn random_board_is_reproducible_from_seed() {
+        assert_eq!(Board::random(8, 6, 0.5, 3), Board::random(8, 6, 0.5, 3));
+        assert_ne!(Board::random(8, 6, 0.5, 3), Board::random(8, 6, 0.5, 4));
+    }
+
+    #[test]
+    fn random_board_population_follows_density() {
+        let population = Board::random(100, 100, 0.25, 7).population();
+
+        assert!((2000..3000).contains(&population));
+    }
+
+    #[test]
+    #[should_panic]
+    fn random_board_with_density_above_one_panics() {
+        Board::random(4, 4, 1.5, 0);
+    }
+
+    #[test]
+    fn to_debug_str_has_axis_labels() {
+        let board = Board::from_str(&["_#_", "_#_", "_#_"].join("\n"), '#');
+
+        let lines: Vec<String> = board
+            .to_debug_str('#', '.')
+            .lines()
+            .map(String::from)
+            .collect();
+
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], "  012");
+        assert!(lines[1].starts_with("0 "));
+        assert!(lines[2].starts_with("1 "));
+        assert!(lines[3].starts_with("2 "));
+        assert!(lines[1..].iter().all(|line| line.ends_with(".#.")));
+    }
+
+    #[test]
+    fn to_debug_str_right_aligns_row_labels() {
+        let board = Board::new(12, 11);
+
+        let debug_str = board.to_debug_str('#', '.');
+        let lines: Vec<&str> = debug_str.lines().collect();
+
+        assert_eq!(lines[0], "   012345678901");
+        assert!(lines[1].starts_with(" 0 "));
+        assert!(lines[11].starts_with("10 "));
+    }
+
+    #[test]
+    fn largest_empty_rectangle_finds_known_region() {
+        let board = Board::from_str(
+            &["#_____", "#_____", "#_____", "######", "_#_#_#"].join("\n"),
+            '#',
+        );
+
+        let (top_left, width, height) = board.largest_empty_rectangle();
+
+        assert_eq!(top_left, Coord::new(1, 0));
+        assert_eq!(width, 5);
+        assert_eq!(height, 3);
+    }
+
+    #[test]
+    fn largest_empty_rectangle_of_empty_board_is_whole_board() {
+        let (top_left, width, height) = Board::new(4, 3).largest_empty_rectangle();
+
+        assert_eq!(top_left, Coord::new(0, 0));
+        assert_eq!(width, 4);
+        assert_eq!(height, 3);
+    }
+
+    #[test]
+    fn largest_empty_rectangle_of_full_board_is_empty() {
+        let board = Board::from_str(&["##", "##"].join("\n"), '#');
+
+        let (_, width, height) = board.largest_empty_rectangle();
+
+        assert_eq!(width * height, 0);
+    }
+
+    #[test]
+    fn perimeter_of_3x3_board_has_8_cells() {
+        let board = Board::new(3, 3);
+
+        let perimeter = board.perimeter_coords();
+
+        assert_eq!(perimeter.len(), 8);
+        assert!(!perimeter.contains(&Coord::new(1, 1)));
+    }
+
+    #[test]
+    fn perimeter_has_no_duplicates() {
+        for (width, height) in [(1, 1), (1, 4), (4, 1), (2, 2), (5, 3)] {
+            let board = Board::new(width, height);
+            let perimeter = board.perimeter_coords();
+
+            for (i, coord) in perimeter.iter().enumerate() {
+                assert!(!perimeter[i + 1..].contains(coord));
+            }
+        }
+    }
+
+    #[test]
+    fn perimeter_of_single_row_is_the_row() {
+        let board = Board::new(4, 1);
+
+        assert_eq!(
+            board.perimeter_coords(),
+            (0..4).map(|x| Coord::new(x, 0)).collect::<Vec<Coord>>()
+        );
+    }
+
+    #[test]
+    fn block_entropy_of_uniform_board_is_zero() {
+        let mut full = Board::new(6, 6);
+        for coord in full.cell_coords().clone() {
+            full.revive_cell(&coord);
+        }
+
+        assert_eq!(Board::new(6, 6).block_entropy(3), 0.0);
+        assert_eq!(full.block_entropy(2), 0.0);
+    }
+
+    #[test]
+    fn block_entropy_of_random_board_is_higher_than_structured_board() {
+        let random = Board::random(32, 32, 0.5, 1);
+        let striped = Board::from_grid(
+            &(0..32)
+                .map(|_| {
+                    (0..32)
+                        .map(|x| {
+                            if x % 2 == 0 {
+                                CellState::Alive
+                            } else {
+                                CellState::Dead
+                            }
+                        })
+                        .collect()
+                })
+                .collect::<Vec<Vec<CellState>>>(),
+        );
+
+        assert!(random.block_entropy(2) > striped.block_entropy(2));
+        assert!(random.block_entropy(2) > 3.0);
+    }
+
+    #[test]
+    fn block_entropy_is_zero_when_block_is_larger_than_board() {
+        assert_eq!(Board::random(3, 3, 0.5, 1).block_entropy(4), 0.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn block_entropy_with_block_size_0_panics() {
+        Board::new(3, 3).block_entropy(0);
+    }
+
+    #[test]
+    fn stamp_text_writes_glyph_pixels() {
+        let mut board = Board::new(5, 7);
+
+        board.stamp_text("A", Coord::new(1, 1), &Font::default());
+
+        // Top of the A
+        assert_eq!(board.get_cell_state(&Coord::new(2, 1)), &CellState::Alive);
+        assert_eq!(board.get_cell_state(&Coord::new(1, 1)), &CellState::Dead);
+        // Crossbar
+        assert_eq!(board.get_cell_state(&Coord::new(1, 3)), &CellState::Alive);
+        assert_eq!(board.get_cell_state(&Coord::new(2, 3)), &CellState::Alive);
+        assert_eq!(board.get_cell_state(&Coord::new(3, 3)), &CellState::Alive);
+        // Legs
+        assert_eq!(board.get_cell_state(&Coord::new(1, 5)), &CellState::Alive);
+        assert_eq!(board.get_cell_state(&Coord::new(2, 5)), &CellState::Dead);
+        assert_eq!(board.get_cell_state(&Coord::new(3, 5)), &CellState::Alive);
+    }
+
+    #[test]
+    fn stamp_text_clips_at_edges() {
+        let mut board = Board::new(4, 4);
+
+        board.stamp_text("88\n88", Coord::new(2, 2), &Font::default());
+
+        assert_eq!(
+            board.to_str('#', '_'),
+            ["____", "____", "__##", "__#_"].join("\n")
+        );
+    }
+
+    #[test]
+    fn creating_board_from_fn_builds_checkerboard() {
+        let board = Board::from_fn(5, 4, |x, y| {
+            if (x + y) % 2 == 0 {
+                CellState::Alive
+            } else {
+                CellState::Dead
+            }
+        });
+
+        assert_eq!(board.width(), 5);
+        assert_eq!(board.height(), 4);
+        for coord in board.cell_coords() {
+            let expected = if (coord.x + coord.y) % 2 == 0 {
+                CellState::Alive
+            } else {
+                CellState::Dead
+            };
+            assert_eq!(board.get_cell_state(coord), &expected);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn creating_board_from_fn_with_0_width_panics() {
+        Board::from_fn(0, 4, |_, _| CellState::Alive);
+    }
+
+    #[test]
+    fn to_svg_has_rect_per_live_cell_and_correct_view_box() {
+        let board = Board::from_str(&["_#_", "__#", "###"].join("\n"), '#');
+
+        let svg = board.to_svg(4, "#000", "#fff");
+
+        assert!(svg.contains(r#"viewBox="0 0 12 12""#));
+        assert_eq!(svg.matches(r##"fill="#000""##).count(), 5);
+        assert_eq!(svg.matches(r##"fill="#fff""##).count(), 1);
+        assert!(svg.ends_with("</svg>"));
+    }
+
+    #[test]
+    fn advanced_leaves_board_untouched_and_matches_game_step() {
+        let board = Board::random(12, 12, 0.4, 5);
+        let original = board.clone();
+        let rules = crate::rules::conways();
+
+        let next = board.advanced(&rules);
+
+        let mut game = Game::new(board.clone(), rules);
+        game.advance_to_next_gen();
+
+        assert_eq!(board, original);
+        assert_eq!(&next, game.board());
+    }
+
+    #[test]
+    fn share_code_of_glider_round_trips_and_is_short() {
+        let mut board = Board::new(40, 40);
+        for (x, y) in [(11, 10), (12, 11), (10, 12), (11, 12), (12, 12)] {
+            board.revive_cell(&Coord::new(x, y));
+        }
+
+        let code = board.to_share_code();
+        let decoded = Board::from_share_code(&code).unwrap();
+
+        assert!(code.len() <= 8);
+        assert!(code
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+        assert_eq!(decoded, crate::patterns::glider());
+    }
+
+    #[test]
+    fn share_code_of_large_board_round_trips() {
+        let board = Board::from_fn(50, 30, |x, y| {
+            if x == 0 || y == 0 || x == 49 || y == 29 || (x * 7 + y * 3) % 5 == 0 {
+                CellState::Alive
+            } else {
+                CellState::Dead
+            }
+        });
+
+        assert_eq!(Board::from_share_code(&board.to_share_code()), Ok(board));
+    }
+
+    #[test]
+    fn share_code_of_empty_board_is_single_dead_cell() {
+        let code = Board::new(10, 10).to_share_code();
+
+        assert_eq!(Board::from_share_code(&code), Ok(Board::new(1, 1)));
+    }
+
+    #[test]
+    fn neighbour_breakdown_splits_orthogonal_and_diagonal() {
+        let board = Board::from_str(
+            &["_____", "_###_", "_#_#_", "___#_", "_____"].join("\n"),
+            '#',
+        );
+
+        assert_eq!(board.neighbour_breakdown(&Coord::new(2, 2)), (3, 3));
+        assert_eq!(board.neighbour_breakdown(&Coord::new(2, 3)), (1, 2));
+    }
+
+    #[test]
+    fn neighbour_breakdown_wraps_around_edges() {
+        let board = Board::from_str(&["____#", "_____", "_____", "#____"].join("\n"), '#');
+
+        assert_eq!(board.neighbour_breakdown(&Coord::new(0, 0)), (2, 0));
+        assert_eq!(board.neighbour_breakdown(&Coord::new(4, 0)), (0, 1));
+    }
+
+    #[test]
+    fn neighbour_breakdown_adds_up_to_live_neighbours() {
+        let board = Board::random(8, 8, 0.5, 9);
+
+        for coord in board.cell_coords() {
+            let (orthogonal, diagonal) = board.neighbour_breakdown(coord);
+
+            assert_eq!(orthogonal + diagonal, board.get_live_neighbours_of(coord));
+        }
+    }
+
+    #[test]
+    fn changes_since_baseline_two_generations_old() {
+        let rules = crate::rules::conways();
+        let baseline = Board::from_str(
+            &["_#____", "__#___", "###___", "______", "______", "______"].join("\n"),
+            '#',
+        );
+        let board = baseline.advanced(&rules).advanced(&rules);
+
+        let changes = board.changes_since(&baseline);
+        assert!(!changes.is_empty());
+
+        let mut caught_up = baseline.clone();
+        for (coord, cell_state) in changes {
+            *caught_up.get_cell_state_mut(&coord) = cell_state;
+        }
+
+        assert_eq!(caught_up, board);
+    }
+
+    #[test]
+    #[should_panic]
+    fn changes_since_panics_on_different_dimensions() {
+        Board::new(3, 3).changes_since(&Board::new(3, 4));
+    }
+
+    #[test]
+    fn from_str_charset_accepts_known_characters() {
+        let board = Board::from_str_charset(&["#_.", "._#"].join("\n"), &['#'], &['_', '.']);
+
+        assert_eq!(
+            board.map(|board| board.to_str('#', '_')),
+            Ok(["#__", "__#"].join("\n"))
+        );
+    }
+
+    #[test]
+    fn from_str_charset_rejects_unexpected_character() {
+        assert_eq!(
+            Board::from_str_charset(&["#_", "_ "].join("\n"), &['#'], &['_']),
+            Err(ParseError::InvalidToken {
+                row: 1,
+                column: 1,
+                token: " ".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn from_str_charset_rejects_ragged_and_empty_input() {
+        assert_eq!(
+            Board::from_str_charset(&["#_", "_"].join("\n"), &['#'], &['_']),
+            Err(ParseError::RaggedRow {
+                row: 1,
+                expected: 2,
+                found: 1,
+            })
+        );
+        assert_eq!(
+            Board::from_str_charset("", &['#'], &['_']),
+            Err(ParseError::Empty)
+        );
+    }
+
+    #[test]
+    fn configuration_census_counts_every_cell_once() {
+        let board = Board::from_str(&["____", "_##_", "_##_", "____"].join("\n"), '#');
+
+        let census = board.configuration_census();
+
+        // Every cell of the block has three live neighbours, and the corners around it have one
+        assert_eq!(census[&(CellState::Alive, 3)], 4);
+        assert_eq!(census[&(CellState::Dead, 1)], 4);
+        assert_eq!(census[&(CellState::Dead, 2)], 8);
+        assert_eq!(census.values().sum::<usize>(), 16);
+    }
+
+    #[test]
+    fn symmetrize_horizontal_mirrors_one_sided_pattern() {
+        let mut board = Board::from_str(&["#____", "_##__", "#____"].join("\n"), '#');
+
+        board.symmetrize(SymmetryKind::Horizontal);
+
+        assert_eq!(
+            board.to_str('#', '_'),
+            ["#___#", "_###_", "#___#"].join("\n")
+        );
+    }
+
+    #[test]
+    fn symmetrize_keeps_existing_cells() {
+        let mut board = Board::from_str(&["#__", "___", "__#"].join("\n"), '#');
+
+        board.symmetrize(SymmetryKind::Vertical);
+
+        assert_eq!(board.to_str('#', '_'), ["#_#", "___", "#_#"].join("\n"));
+    }
+
+    #[test]
+    fn symmetrize_diagonal_swaps_rows_and_columns() {
+        let mut board = Board::from_str(&["_##", "___", "___"].join("\n"), '#');
+
+        board.symmetrize(SymmetryKind::Diagonal);
+
+        assert_eq!(board.to_str('#', '_'), ["_##", "#__", "#__"].join("\n"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn symmetrize_diagonal_panics_on_non_square_board() {
+        Board::new(3, 2).symmetrize(SymmetryKind::Diagonal);
+    }
+
+    #[test]
+    fn symmetry_score_of_block_is_one() {
+        let board = Board::from_str(&["____", "_##_", "_##_", "____"].join("\n"), '#');
+
+        for kind in [
+            SymmetryKind::Horizontal,
+            SymmetryKind::Vertical,
+            SymmetryKind::Diagonal,
+            SymmetryKind::FourFold,
+        ] {
+            assert_eq!(board.symmetry_score(kind), 1.0);
+        }
+    }
+
+    #[test]
+    fn symmetry_score_of_slightly_asymmetric_pattern_is_below_one() {
+        let board = Board::from_str(
+            &["_____", "_###_", "_#_#_", "_##__", "_____"].join("\n"),
+            '#',
+        );
+
+        let score = board.symmetry_score(SymmetryKind::Horizontal);
+
+        // Only the two outer cells of the bottom row disagree
+        assert_eq!(score, 7.0 / 9.0);
+        // Reflecting along the diagonal does not change the pattern
+        assert_eq!(board.symmetry_score(SymmetryKind::Diagonal), 1.0);
+    }
+
+    #[test]
+    fn get_neighbour_coords_with_dead_boundary_leaves_out_cells_outside_board() {
+        let mut board = Board::new(4, 3);
+        board.set_boundary(Boundary::Dead);
+
+        assert_eq!(board.get_neighbour_coords(&Coord::new(0, 0)).len(), 3);
+        assert_eq!(board.get_neighbour_coords(&Coord::new(3, 1)).len(), 5);
+        assert_eq!(board.get_neighbour_coords(&Coord::new(1, 1)).len(), 8);
+    }
+
+    #[test]
+    fn neighbour_breakdown_with_dead_boundary_does_not_wrap() {
+        let mut board = Board::from_str(&["#___#", "_____", "#____"].join("\n"), '#');
+        board.set_boundary(Boundary::Dead);
+
+        assert_eq!(board.neighbour_breakdown(&Coord::new(0, 0)), (0, 0));
+        assert_eq!(board.neighbour_breakdown(&Coord::new(0, 1)), (2, 0));
+    }
+
+    #[test]
+    #[cfg(feature = "png")]
+    fn to_annotated_png_has_expected_dimensions() {
+        let board = Board::from_str(&["_#__", "_#__", "_#__"].join("\n"), '#');
+
+        let png = board
+            .to_annotated_png(10, &Colors::default(), &Font::default())
+            .unwrap();
+
+        let decoder = png::Decoder::new(std::io::Cursor::new(png));
+        let reader = decoder.read_info().unwrap();
+        let info = reader.info();
+        assert_eq!((info.width, info.height), (40, 30));
+    }
+
+    #[test]
+    #[cfg(feature = "png")]
+    fn to_annotated_png_works_with_cells_smaller_than_glyphs() {
+        let board = Board::from_str(&["##", "#_"].join("\n"), '#');
+
+        assert!(board
+            .to_annotated_png(2, &Colors::default(), &Font::default())
+            .is_ok());
+    }
+
+    #[test]
+    fn live_convex_hull_of_l_shape() {
+        let board = Board::from_str(&["#___", "#___", "#___", "###_"].join("\n"), '#');
+
+        assert_eq!(
+            board.live_convex_hull(),
+            vec![Coord::new(0, 0), Coord::new(2, 3), Coord::new(0, 3)]
+        );
+    }
+
+    #[test]
+    fn live_convex_hull_of_degenerate_patterns() {
+        assert_eq!(Board::new(3, 3).live_convex_hull(), vec![]);
+
+        let single = Board::from_str(&["___", "_#_"].join("\n"), '#');
+        assert_eq!(single.live_convex_hull(), vec![Coord::new(1, 1)]);
+
+        let line = Board::from_str(&["#___", "_#__", "__#_"].join("\n"), '#');
+        assert_eq!(
+            line.live_convex_hull(),
+            vec![Coord::new(0, 0), Coord::new(2, 2)]
+        );
+    }
+
+    #[test]
+    fn to_str_recentered_joins_block_split_across_corners() {
+        let board = Board::from_str(&["#__#", "____", "____", "#__#"].join("\n"), '#');
+
+        assert_eq!(
+            board.to_str_recentered('#', '_'),
+            ["____", "_##_", "_##_", "____"].join("\n")
+        );
+    }
+
+    #[test]
+    fn to_str_recentered_leaves_empty_board_alone() {
+        let board = Board::new(3, 2);
+
+        assert_eq!(board.to_str_recentered('#', '_'), board.to_str('#', '_'));
+    }
+
+    #[test]
+    fn display_matches_to_str_with_hash_and_space() {
+        let board = Board::with_glider_stream(8, 8, 1, 4);
+
+        assert_eq!(format!("{}", board), board.to_str('#', ' '));
+    }
+
+    #[test]
+    fn tiled_block_has_expected_dimensions_and_cells() {
+        let block = Board::from_str(&["##", "##"].join("\n"), '#');
+
+        let board = Board::tiled(&block, 3, 2);
+
+        assert_eq!((board.width(), board.height()), (6, 4));
+        assert_eq!(board.population(), 24);
+        assert_eq!(board.get_cell_state(&Coord::new(5, 3)), &CellState::Alive);
+    }
+
+    #[test]
+    #[should_panic]
+    fn tiled_with_no_repeats_panics() {
+        Board::tiled(&Board::new(2, 2), 0, 1);
+    }
+
+    #[test]
+    fn to_rle_keeps_dead_border_and_round_trips() {
+        let board = Board::from_str(
+            &["______", "__##__", "__##__", "______", "______"].join("\n"),
+            '#',
+        );
+
+        let rle = board.to_rle();
+
+        assert_eq!(rle, ["x = 6, y = 5", "$2b2o$2b2o!"].join("\n"));
+        assert_eq!(Board::from_rle(&rle), Ok(board));
+    }
+
+    #[test]
+    fn to_rle_of_empty_board_round_trips() {
+        let board = Board::new(4, 3);
+
+        assert_eq!(Board::from_rle(&board.to_rle()), Ok(board));
+    }
+
+    #[test]
+    fn from_cells_pads_short_rows_and_keeps_empty_rows() {
+        let board = Board::from_cells(&["!Name: Two dots", "O..O", "", "O"].join("\r\n"));
+
+        assert_eq!(board.to_cells(), ["O..O", "....", "O..."].join("\n"));
+    }
+
+    #[test]
+    fn to_cells_round_trips() {
+        let board = Board::with_glider_stream(10, 10, 2, 5);
+
+        assert_eq!(Board::from_cells(&board.to_cells()), board);
+    }
+
+    #[test]
+    fn from_apgcode_of_block() {
+        let board = Board::from_apgcode("xs4_33").unwrap();
+
+        assert_eq!(board.to_str('#', '_'), ["##", "##"].join("\n"));
+    }
+
+    #[test]
+    fn from_apgcode_handles_gaps_and_strips() {
+        // A blinker, then cells separated by a gap of seven columns and by a new strip
+        assert_eq!(
+            Board::from_apgcode("xp2_7").unwrap().to_str('#', '_'),
+            ["#", "#", "#"].join("\n")
+        );
+        let board = Board::from_apgcode("xs2_1y31z01").unwrap();
+        assert_eq!((board.width(), board.height()), (9, 6));
+        assert_eq!(board.population(), 3);
+        assert_eq!(board.get_cell_state(&Coord::new(8, 0)), &CellState::Alive);
+        assert_eq!(board.get_cell_state(&Coord::new(1, 5)), &CellState::Alive);
+    }
+
+    #[test]
+    fn from_apgcode_rejects_invalid_codes() {
+        assert!(Board::from_apgcode("33").is_err());
+        assert!(Board::from_apgcode("xz4_33").is_err());
+        assert_eq!(Board::from_apgcode("xs0_"), Err(ParseError::Empty));
+        assert_eq!(
+            Board::from_apgcode("xs4_3!"),
+            Err(ParseError::InvalidToken {
+                row: 0,
+                column: 5,
+                token: "!".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn von_neumann_neighbourhood_counts_only_orthogonal_neighbours() {
+        let mut board = Board::from_str(&["###", "###", "###"].join("\n"), '#');
+        assert_eq!(board.get_live_neighbours_of(&Coord::new(1, 1)), 8);
+
+        board.set_neighbourhood(Neighbourhood::VonNeumann);
+
+        assert_eq!(board.get_live_neighbours_of(&Coord::new(1, 1)), 4);
+        assert_eq!(
+            board.get_neighbour_coords(&Coord::new(1, 1)),
+            vec![
+                Coord::new(0, 1),
+                Coord::new(2, 1),
+                Coord::new(1, 0),
+                Coord::new(1, 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn von_neumann_neighbourhood_with_dead_boundary() {
+        let mut board = Board::new(3, 3);
+        board.set_neighbourhood(Neighbourhood::VonNeumann);
+        board.set_boundary(Boundary::Dead);
+
+        assert_eq!(board.get_neighbour_coords(&Coord::new(0, 0)).len(), 2);
+    }
+
+    #[test]
+    fn clear_region_carves_hole_in_filled_board() {
+        let mut board = Board::from_fn(5, 5, |_, _| CellState::Alive);
+
+        board.clear_region(Coord::new(1, 1), 3, 3);
+
+        assert_eq!(
+            board.to_str('#', '_'),
+            ["#####", "#___#", "#___#", "#___#", "#####"].join("\n")
+        );
+    }
+
+    #[test]
+    fn clear_region_outside_board_does_nothing() {
+        let mut board = Board::from_fn(3, 3, |_, _| CellState::Alive);
+
+        board.clear_region(Coord::new(5, 0), 2, 2);
+        board.clear_region(Coord::new(0, 0), 0, 3);
+
+        assert_eq!(board.population(), 9);
+    }
+
+    #[test]
+    fn live_components_groups_touching_cells() {
+        let board = Board::from_str(
+            &["##___#", "#_____", "______", "__##__", "______"].join("\n"),
+            '#',
+        );
+
+        let mut sizes: Vec<usize> = board.live_components().iter().map(Vec::len).collect();
+        sizes.sort();
+
+        // The top right cell wraps around to touch the top left group
+        assert_eq!(sizes, vec![2, 4]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialized_board_round_trips() {
+        let mut board = Board::from_str(&["_#_", "__#", "###", "___"].join("\n"), '#');
+        board.set_boundary(Boundary::Dead);
+        board.set_neighbourhood(Neighbourhood::VonNeumann);
+
+        let json = serde_json::to_string(&board).unwrap();
+
+        assert_eq!(serde_json::from_str::<Board>(&json).unwrap(), board);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserializing_board_with_ragged_rows_fails() {
+        let json = r#"{
+            "cells": [["Alive", "Dead"], ["Dead"]],
+            "boundary": "Toroidal",
+            "neighbourhood": "Moore"
+        }"#;
+
+        assert!(serde_json::from_str::<Board>(json).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserializing_board_without_cells_fails() {
+        let json = r#"{ "cells": [[]], "boundary": "Toroidal", "neighbourhood": "Moore" }"#;
+
+        assert!(serde_json::from_str::<Board>(json).is_err());
+    }
+
+    #[test]
+    fn neighbours_of_interior_cell_do_not_wrap() {
+        let board = Board::new(4, 4);
+
+        assert!(!board.neighbours_wrapped(&Coord::new(1, 2)));
+    }
+
+    #[test]
+    fn neighbours_of_edge_cells_wrap_on_toroidal_board() {
+        let board = Board::new(4, 4);
+
+        for coord in [Coord::new(0, 0), Coord::new(3, 1), Coord::new(2, 3)] {
+            assert!(board.neighbours_wrapped(&coord));
+        }
+    }
 
     #[test]
-    #[should_panic]
-    fn creating_board_with_0_width_panics() {
-        Board::new(0, 10);
+    fn checked_cell_access_outside_board_leaves_board_untouched() {
+        let mut board = Board::from_str(&["#_", "_#"].join("\n"), '#');
+        let original = board.clone();
+
+        for coord in [
+            Coord::new(2, 0),
+            Coord::new(0, 2),
+            Coord::new(usize::MAX, 1),
+        ] {
+            assert_eq!(board.try_get_cell_state(&coord), None);
+            assert!(!board.try_kill_cell(&coord));
+            assert!(!board.try_revive_cell(&coord));
+            assert!(!board.try_toggle_cell(&coord));
+        }
+
+        assert_eq!(board, original);
     }
 
     #[test]
-    #[should_panic]
-    fn creating_board_with_0_height_panics() {
-        Board::new(10, 0);
+    fn live_bounds_of_single_cell_is_that_cell() {
+        let mut board = Board::new(5, 4);
+        board.revive_cell(&Coord::new(3, 2));
+
+        assert_eq!(
+            board.live_bounds(),
+            Some((Coord::new(3, 2), Coord::new(3, 2)))
+        );
     }
 
     #[test]
-    #[should_panic]
-    fn creating_board_with_0_cells_panics() {
-        Board::new(0, 0);
+    fn subboard_reaching_outside_board_is_dead_there() {
+        let board = Board::from_str(&["___", "_##", "_##"].join("\n"), '#');
+
+        let sub = board.subboard(&Coord::new(1, 1), 4, 3);
+
+        assert_eq!(
+            sub,
+            Board::from_str(&["##__", "##__", "____"].join("\n"), '#')
+        );
+        assert!(board.subboard(&Coord::new(usize::MAX, 0), 2, 2).is_empty());
     }
 
     #[test]
-    fn all_cells_initially_dead() {
-        let height = 10;
-        let width = 5;
-        let board = Board::new(width, height);
+    fn live_on_diagonals_wraps_around_corners() {
+        let board = Board::from_str(&["___#", "____", "#___", "_#_#"].join("\n"), '#');
 
-        println!("{:?}", board.cell_coords());
+        // The opposite corner wraps around to the north west of the top left corner, and the cell
+        // at (1, 3) to its north east. The top right corner wraps around to its west
+        assert_eq!(board.live_on_diagonals(&Coord::new(0, 0)), 2);
+        assert_eq!(board.neighbour_breakdown(&Coord::new(0, 0)), (1, 2));
+    }
 
-        let all_dead = board
-            .cell_coords()
-            .iter()
-            .map(|c| board.get_cell_state(c))
-            .all(|s| s == &CellState::Dead);
+    #[test]
+    fn stamp_copy_overwrites_dead_cells_too() {
+        let mut board = Board::from_str(&["####", "####", "####"].join("\n"), '#');
+        let pattern = Board::from_str(&["#_", "_#"].join("\n"), '#');
 
-        assert!(all_dead);
+        board.stamp(&pattern, &Coord::new(1, 0), StampMode::Copy);
+
+        assert_eq!(
+            board,
+            Board::from_str(&["##_#", "#_##", "####"].join("\n"), '#')
+        );
     }
 
     #[test]
-    #[should_panic]
-    fn creating_board_from_grid_with_0_cells_panics() {
-        Board::from_grid(&Vec::new());
+    fn stamp_clips_pattern_at_board_edges() {
+        let mut board = Board::new(4, 3);
+        board.set_boundary(Boundary::Dead);
+        let pattern = Board::from_str(&["###", "###"].join("\n"), '#');
+
+        board.stamp(&pattern, &Coord::new(2, 2), StampMode::Or);
+        board.stamp(&pattern, &Coord::new(usize::MAX, 0), StampMode::Or);
+
+        assert_eq!(board.to_str('#', '_'), ["____", "____", "__##"].join("\n"));
     }
 
     #[test]
-    fn creating_board_from_grid_gets_correct_dimensions() {
-        let grid = vec![vec![CellState::Alive, CellState::Dead]];
+    fn rotating_glider_clockwise_turns_it_from_south_east_to_south_west() {
+        let mut board = Board::new(8, 8);
+        board.stamp(
+            &patterns::glider().rotate_cw(),
+            &Coord::new(3, 2),
+            StampMode::Or,
+        );
+        let mut game = Game::new(board, crate::rules::conways());
+        let (before, _) = game.board().live_bounds().unwrap();
 
-        let board = Board::from_grid(&grid);
+        game.advance_generations(4);
+        let (after, _) = game.board().live_bounds().unwrap();
 
-        assert_eq!(board.width(), 2);
-        assert_eq!(board.height(), 1);
+        assert_eq!(after, Coord::new(before.x - 1, before.y + 1));
     }
 
     #[test]
-    fn cearing_board_from_grid_with_uneven_rows_works() {
-        let grid = vec![
-            vec![],
-            vec![CellState::Dead, CellState::Alive],
-            vec![CellState::Dead],
-        ];
+    fn rotating_four_times_gives_same_board() {
+        let mut board = Board::random(7, 4, 0.5, 11);
+        board.set_boundary(Boundary::Dead);
 
-        let board = Board::from_grid(&grid);
+        let rotated = board.rotate_cw();
 
-        assert_eq!(board.width(), 2);
-        assert_eq!(board.height(), 3);
+        assert_eq!(rotated.width(), 4);
+        assert_eq!(rotated.height(), 7);
+        assert_eq!(rotated.cell_coords().len(), 28);
+        assert_eq!(rotated.boundary(), Boundary::Dead);
+        assert_eq!(rotated.rotate_ccw(), board);
+        assert_eq!(board.rotate_cw().rotate_cw().rotate_cw().rotate_cw(), board);
     }
 
     #[test]
-    #[should_panic]
-    fn creating_board_from_empty_string_panics() {
-        Board::from_str("", '#');
+    fn set_row_only_changes_that_row() {
+        let original = Board::random(6, 5, 0.5, 4);
+        let mut board = original.clone();
+
+        board.set_row(3, CellState::Alive);
+
+        for coord in board.cell_coords() {
+            if coord.y == 3 {
+                assert_eq!(board.get_cell_state(coord), &CellState::Alive);
+            } else {
+                assert_eq!(board.get_cell_state(coord), original.get_cell_state(coord));
+            }
+        }
     }
 
     #[test]
-    #[should_panic]
-    fn creating_board_from_string_with_only_newlines_panics() {
-        Board::from_str("\n\n\n\n\n", '#');
+    fn set_column_kills_whole_column() {
+        let mut board = Board::from_str(&["###", "###"].join("\n"), '#');
+
+        board.set_column(0, CellState::Dead);
+
+        assert_eq!(board, Board::from_str(&["_##", "_##"].join("\n"), '#'));
     }
 
     #[test]
     #[should_panic]
-    fn creating_board_from_string_with_only_carriage_returns_panics() {
-        Board::from_str("\r\r\r\r", '#');
+    fn set_row_outside_board_panics() {
+        Board::new(3, 3).set_row(3, CellState::Alive);
     }
 
     #[test]
-    #[should_panic]
-    fn creating_board_from_string_with_newline_as_alive_char_panics() {
-        Board::from_str("#", '\n');
+    fn pulsar_is_unchanged_by_flips() {
+        let pulsar = Board::from_str(
+            &[
+                "_______________",
+                "___###___###___",
+                "_______________",
+                "_#____#_#____#_",
+                "_#____#_#____#_",
+                "_#____#_#____#_",
+                "___###___###___",
+                "_______________",
+                "___###___###___",
+                "_#____#_#____#_",
+                "_#____#_#____#_",
+                "_#____#_#____#_",
+                "_______________",
+                "___###___###___",
+                "_______________",
+            ]
+            .join("\n"),
+            '#',
+        );
+
+        assert_eq!(pulsar.flip_horizontal(), pulsar);
+        assert_eq!(pulsar.flip_vertical(), pulsar);
     }
 
     #[test]
-    #[should_panic]
-    fn creating_board_from_string_with_carriage_return_as_alive_char_panics() {
-        Board::from_str("#", '\r');
+    fn flipping_glider_changes_its_direction() {
+        let glider = patterns::glider();
+
+        assert_ne!(glider.flip_horizontal(), glider);
+        assert_eq!(glider.flip_horizontal().width(), glider.width());
+        assert_eq!(glider.flip_vertical().height(), glider.height());
+        assert_eq!(glider.flip_horizontal().flip_horizontal(), glider);
     }
 
     #[test]
-    fn creating_board_from_string_ignores_carriage_returns() {
-        let board = Board::from_str(&vec!["##", "##"].join("\n"), '#');
+    fn indexing_matches_cell_accessors() {
+        let mut board = Board::random(5, 4, 0.5, 2);
 
-        assert_eq!(board.width(), 2);
-        assert_eq!(board.height(), 2);
+        for coord in board.cell_coords().clone() {
+            assert_eq!(&board[coord.clone()], board.get_cell_state(&coord));
+        }
+
+        board[Coord::new(4, 3)] = CellState::Dead;
+        board[Coord::new(4, 3)].toggle();
+
+        assert_eq!(board.get_cell_state(&Coord::new(4, 3)), &CellState::Alive);
     }
 
     #[test]
-    fn creating_board_from_string_trailing_newline() {
-        // Trailing newline causes a bottom row with dead cells
-        let board = Board::from_str("##\n##\n", '#');
+    fn live_cells_yields_every_live_cell_once() {
+        let board = Board::random(9, 7, 0.3, 21);
 
-        assert_eq!(board.width(), 2);
-        assert_eq!(board.height(), 3);
+        let live: Vec<&Coord> = board.live_cells().collect();
+
+        assert_eq!(live.len(), board.population());
+        assert!(live
+            .iter()
+            .all(|c| board.get_cell_state(c) == &CellState::Alive));
+        assert_eq!(Board::new(3, 3).live_cells().count(), 0);
     }
 
     #[test]
-    fn creating_board_from_string_counts_non_live_characters_as_dead() {
-        let board = Board::from_str(&vec!["*T#5.", " #_#?"].join("\n"), '#');
+    fn creating_board_from_indented_string_matches_unindented() {
+        let unindented = Board::from_str(&["_#_", "__#", "###"].join("\n"), '#');
 
-        assert_eq!(board.get_cell_state(&Coord::new(0, 0)), &CellState::Dead);
-        assert_eq!(board.get_cell_state(&Coord::new(1, 0)), &CellState::Dead);
-        assert_eq!(board.get_cell_state(&Coord::new(2, 0)), &CellState::Alive);
-        assert_eq!(board.get_cell_state(&Coord::new(3, 0)), &CellState::Dead);
-        assert_eq!(board.get_cell_state(&Coord::new(4, 0)), &CellState::Dead);
-        assert_eq!(board.get_cell_state(&Coord::new(0, 1)), &CellState::Dead);
-        assert_eq!(board.get_cell_state(&Coord::new(1, 1)), &CellState::Alive);
-        assert_eq!(board.get_cell_state(&Coord::new(2, 1)), &CellState::Dead);
-        assert_eq!(board.get_cell_state(&Coord::new(3, 1)), &CellState::Alive);
-        assert_eq!(board.get_cell_state(&Coord::new(4, 1)), &CellState::Dead);
+        let indented = Board::from_str_aligned(
+            &["", "        _#_", "        __#", "        ###", "    "].join("\n"),
+            '#',
+        );
+        let tabbed = Board::from_str_aligned(&["\t\t_#_", "\t\t__#", "\t\t###"].join("\n"), '#');
+
+        assert_eq!(indented, unindented);
+        assert_eq!(tabbed, unindented);
     }
 
     #[test]
-    #[should_panic]
-    fn to_str_panics_if_alive_char_is_newline() {
-        let board = Board::new(1, 1);
+    fn creating_board_from_aligned_string_keeps_relative_indentation() {
+        let board = Board::from_str_aligned(&["   #", "  # ", "    #"].join("\n"), '#');
 
-        board.to_str('\n', ' ');
+        assert_eq!(board, Board::from_str(&[" #", "# ", "  #"].join("\n"), '#'));
     }
 
     #[test]
-    #[should_panic]
-    fn to_str_panics_if_alive_char_is_carriage_return() {
-        let board = Board::new(1, 1);
+    fn clear_keeps_board_size_and_settings() {
+        let mut board = Board::random(6, 4, 0.5, 8);
+        board.set_boundary(Boundary::Dead);
 
-        board.to_str('\r', ' ');
+        board.clear();
+
+        let mut expected = Board::new(6, 4);
+        expected.set_boundary(Boundary::Dead);
+        assert_eq!(board, expected);
     }
 
     #[test]
-    #[should_panic]
-    fn to_str_panics_if_dead_char_is_newline() {
-        let board = Board::new(1, 1);
+    fn shrinking_board_drops_cells_outside_new_size() {
+        let mut board = Board::from_str(&["#__#", "_##_", "#__#"].join("\n"), '#');
 
-        board.to_str('#', '\n');
+        board.resize(2, 2);
+
+        assert_eq!(board, Board::from_str(&["#_", "_#"].join("\n"), '#'));
+        assert_eq!(board.cell_coords().len(), 4);
+    }
+
+    #[test]
+    fn grown_board_has_working_neighbours() {
+        let mut board = Board::from_str(&["_#_", "_#_", "_#_"].join("\n"), '#');
+
+        board.resize(5, 5);
+
+        assert_eq!(board.cell_coords().len(), 25);
+        assert_eq!(board.get_live_neighbours_of(&Coord::new(0, 1)), 3);
+        // Without the old wrap around the 3x3 board, the far right cells see nothing
+        assert_eq!(board.get_live_neighbours_of(&Coord::new(4, 1)), 0);
     }
 
     #[test]
     #[should_panic]
-    fn to_str_panics_if_dead_char_is_carriage_return() {
-        let board = Board::new(1, 1);
+    fn resizing_board_to_0_width_panics() {
+        Board::new(3, 3).resize(0, 3);
+    }
 
-        board.to_str('#', '\r');
+    #[test]
+    fn live_count_matches_fresh_count_through_all_changes() {
+        let mut board = Board::random(8, 6, 0.4, 13);
+        let check = |board: &Board| assert_eq!(board.population(), board.live_cells().count());
+
+        check(&board);
+
+        for coord in board.cell_coords().clone().iter().step_by(3) {
+            board.toggle_cell(coord);
+            check(&board);
+        }
+
+        board.revive_cell(&Coord::new(0, 0));
+        board.revive_cell(&Coord::new(0, 0));
+        check(&board);
+        board.kill_cell(&Coord::new(1, 0));
+        board.kill_cell(&Coord::new(1, 0));
+        check(&board);
+        board.try_toggle_cell(&Coord::new(9, 9));
+        check(&board);
+        board.set_cell_state(&Coord::new(2, 2), CellState::Alive);
+        check(&board);
+        board.set_row(1, CellState::Alive);
+        check(&board);
+        board.set_column(4, CellState::Dead);
+        check(&board);
+        board.clear_region(Coord::new(5, 3), 10, 10);
+        check(&board);
+
+        board[Coord::new(7, 0)] = CellState::Alive;
+        check(&board);
+        board.toggle_cell(&Coord::new(6, 0));
+        check(&board);
+
+        board.clear();
+        check(&board);
     }
 
     #[test]
-    fn gets_and_sets_state_of_cell() {
-        let height = 10;
-        let width = 5;
-        let mut board = Board::new(width, height);
-        let coord = Coord::new(0, 0);
+    fn live_neighbour_counts_match_counting_each_cell() {
+        for (boundary, neighbourhood) in [
+            (Boundary::Toroidal, Neighbourhood::Moore),
+            (Boundary::Dead, Neighbourhood::Moore),
+            (Boundary::Toroidal, Neighbourhood::VonNeumann),
+            (Boundary::Dead, Neighbourhood::VonNeumann),
+            (Boundary::KleinBottle, Neighbourhood::Moore),
+            (Boundary::VerticalKleinBottle, Neighbourhood::Moore),
+            (Boundary::ProjectivePlane, Neighbourhood::Moore),
+        ] {
+            for (width, height) in [(9, 7), (2, 3), (1, 1)] {
+                let mut board = Board::random(width, height, 0.5, 6);
+                board.set_boundary(boundary);
+                board.set_neighbourhood(neighbourhood);
 
-        assert_eq!(board.get_cell_state(&coord), &CellState::Dead);
+                let expected: Vec<u8> = board
+                    .cell_coords()
+                    .iter()
+                    .map(|c| board.get_live_neighbours_of(c))
+                    .collect();
 
-        board.revive_cell(&coord);
+                assert_eq!(board.live_neighbour_counts(), expected);
+            }
+        }
+    }
 
-        assert_eq!(board.get_cell_state(&coord), &CellState::Alive);
+    #[test]
+    fn classify_transition_of_blinker_step() {
+        let board = Board::from_str(
+            &["_____", "__#__", "__#__", "__#__", "_____"].join("\n"),
+            '#',
+        );
+        let next = board.advanced(&crate::rules::conways());
 
-        board.kill_cell(&coord);
+        let transitions = board.classify_transition(&next);
 
-        assert_eq!(board.get_cell_state(&coord), &CellState::Dead);
+        assert_eq!(transitions[2][1], CellTransition::Born);
+        assert_eq!(transitions[2][3], CellTransition::Born);
+        assert_eq!(transitions[2][2], CellTransition::Survived);
+        assert_eq!(transitions[1][2], CellTransition::Died);
+        assert_eq!(transitions[3][2], CellTransition::Died);
+        assert_eq!(transitions[0][0], CellTransition::StayedDead);
+        assert_eq!(transitions[1][1], CellTransition::StayedDead);
+        assert_eq!(transitions.len(), 5);
+        assert!(transitions.iter().all(|row| row.len() == 5));
+    }
 
-        board.toggle_cell(&coord);
+    #[test]
+    #[should_panic]
+    fn classify_transition_of_different_sizes_panics() {
+        Board::new(3, 3).classify_transition(&Board::new(3, 4));
+    }
 
-        assert_eq!(board.get_cell_state(&coord), &CellState::Alive);
+    #[test]
+    fn klein_bottle_mirrors_neighbours_across_right_edge() {
+        let mut board = Board::new(5, 5);
+        board.set_boundary(Boundary::KleinBottle);
 
-        board.toggle_cell(&coord);
+        let neighbours = board.get_neighbour_coords(&Coord::new(4, 1));
 
-        assert_eq!(board.get_cell_state(&coord), &CellState::Dead);
+        // East of the right edge is the left edge, upside down
+        assert!(neighbours.contains(&Coord::new(0, 4)));
+        assert!(neighbours.contains(&Coord::new(0, 3)));
+        assert!(neighbours.contains(&Coord::new(0, 2)));
+        assert!(!neighbours.contains(&Coord::new(0, 1)));
+        assert!(!neighbours.contains(&Coord::new(0, 0)));
+        assert_eq!(neighbours.len(), 8);
+
+        // The top and bottom edges are joined without a twist
+        assert!(board
+            .get_neighbour_coords(&Coord::new(1, 0))
+            .contains(&Coord::new(1, 4)));
     }
 
     #[test]
-    fn neighbours_should_be_correct_in_middle_of_board() {
-        let height = 10;
-        let width = 5;
-        let board = Board::new(width, height);
-        let coord = Coord::new(1, 1);
+    fn projective_plane_mirrors_neighbours_across_both_edges() {
+        let mut board = Board::new(5, 4);
+        board.set_boundary(Boundary::ProjectivePlane);
 
-        let expected_neighbours = vec![
-            Coord::new(0, 1),
-            Coord::new(2, 1),
-            Coord::new(1, 0),
-            Coord::new(1, 2),
+        // East of the right edge is the left edge, upside down
+        assert!(board
+            .get_neighbour_coords(&Coord::new(4, 1))
+            .contains(&Coord::new(0, 2)));
+        // South of the bottom edge is the top edge, mirrored
+        assert!(board
+            .get_neighbour_coords(&Coord::new(1, 3))
+            .contains(&Coord::new(3, 0)));
+    }
+
+    #[test]
+    fn projective_plane_corners_have_distinct_neighbours_other_than_themselves() {
+        let mut board = Board::new(5, 5);
+        board.set_boundary(Boundary::ProjectivePlane);
+
+        for corner in [
             Coord::new(0, 0),
-            Coord::new(0, 2),
-            Coord::new(2, 0),
-            Coord::new(2, 2),
-        ];
+            Coord::new(4, 0),
+            Coord::new(0, 4),
+            Coord::new(4, 4),
+        ] {
+            let neighbours = board.get_neighbour_coords(&corner);
+            let distinct: HashSet<&Coord> = neighbours.iter().collect();
 
-        let neighbours = board.get_neighbour_coords(&coord);
+            assert!(!neighbours.contains(&corner));
+            assert_eq!(distinct.len(), neighbours.len());
+            for neighbour in &neighbours {
+                assert!(board.get_neighbour_coords(neighbour).contains(&corner));
+            }
 
-        for expected in expected_neighbours {
-            assert!(neighbours.contains(&expected));
+            board.revive_cell(&corner);
+            assert_eq!(board.get_live_neighbours_of(&corner), 0);
+            board.kill_cell(&corner);
         }
+
+        // Opposite corners are the same point, so they neighbour each other
+        assert!(board
+            .get_neighbour_coords(&Coord::new(0, 0))
+            .contains(&Coord::new(4, 4)));
+        assert!(board
+            .get_neighbour_coords(&Coord::new(4, 0))
+            .contains(&Coord::new(0, 4)));
     }
 
     #[test]
-    fn neighbours_should_be_correct_upper_left() {
-        let height = 10;
-        let width = 5;
-        let board = Board::new(width, height);
-        let coord = Coord::new(0, 0);
+    fn rotating_klein_bottle_moves_twist_to_other_edges() {
+        let mut board = Board::random(7, 5, 0.4, 8);
+        board.set_boundary(Boundary::KleinBottle);
+        let conways = crate::rules::conways();
 
-        let expected_neighbours = vec![
-            Coord::new(width - 1, 0),
-            Coord::new(1, 0),
-            Coord::new(0, height - 1),
-            Coord::new(0, 1),
-            Coord::new(width - 1, height - 1),
-            Coord::new(width - 1, 1),
-            Coord::new(1, height - 1),
-            Coord::new(1, 1),
-        ];
+        let rotated = board.rotate_cw();
+        assert_eq!(rotated.boundary(), Boundary::VerticalKleinBottle);
+        assert_eq!(rotated.rotate_ccw().boundary(), Boundary::KleinBottle);
 
-        let neighbours = board.get_neighbour_coords(&coord);
+        // Stepping commutes with rotating only if the twist follows the rotation
+        let (mut stepped, mut rotated_stepped) = (board.clone(), rotated);
+        for _ in 0..6 {
+            stepped = stepped.advanced(&conways);
+            rotated_stepped = rotated_stepped.advanced(&conways);
 
-        for expected in expected_neighbours {
-            assert!(neighbours.contains(&expected));
+            assert_eq!(stepped.rotate_cw(), rotated_stepped);
+            assert_eq!(rotated_stepped.rotate_ccw(), stepped);
         }
     }
 
     #[test]
-    fn neighbours_should_be_correct_lower_right() {
-        let height = 10;
-        let width = 5;
-        let board = Board::new(width, height);
-        let coord = Coord::new(width - 1, height - 1);
+    fn stamp_wraps_oversized_offsets_on_toroidal_board() {
+        let mut board = Board::new(6, 5);
 
-        let expected_neighbours = vec![
-            Coord::new(0, 0),
-            Coord::new(0, height - 1),
-            Coord::new(0, height - 2),
-            Coord::new(width - 1, 0),
-            Coord::new(width - 2, 0),
-            Coord::new(width - 2, height - 2),
-            Coord::new(width - 1, height - 2),
-            Coord::new(width - 2, height - 1),
-        ];
+        board.stamp(
+            &patterns::block(),
+            &Coord::new(6 + 2, 5 * 3 + 1),
+            StampMode::Or,
+        );
 
-        let neighbours = board.get_neighbour_coords(&coord);
+        let mut expected = Board::new(6, 5);
+        expected.stamp(&patterns::block(), &Coord::new(2, 1), StampMode::Or);
+        assert_eq!(board, expected);
+        assert_eq!(board.population(), 4);
+    }
 
-        println!("{:?}", neighbours);
-        println!("{:?}", expected_neighbours);
+    #[test]
+    fn stamp_clips_oversized_offsets_on_dead_boundary() {
+        let mut board = Board::new(6, 5);
+        board.set_boundary(Boundary::Dead);
 
-        for expected in expected_neighbours {
-            assert!(neighbours.contains(&expected));
-        }
+        board.stamp(&patterns::block(), &Coord::new(6 + 2, 0), StampMode::Or);
+        board.stamp(
+            &patterns::block(),
+            &Coord::new(usize::MAX, 0),
+            StampMode::Or,
+        );
+
+        assert!(board.is_empty());
     }
 
     #[test]
-    fn three_live_neighbours() {
-        let board = Board::from_str(
-            &vec!["_____", "_##__", "_____", "__#__", "_____"].join("\n"),
-            '#',
+    fn from_apgcode_rejects_huge_patterns_before_allocating() {
+        let code = format!("xs2_1{}{}1", "z".repeat(20_000), "yz".repeat(10_000));
+
+        assert_eq!(Board::from_apgcode(&code), Err(ParseError::TooLarge));
+    }
+
+    #[test]
+    fn from_apgcode_rejects_uppercase_digits() {
+        assert_eq!(
+            Board::from_apgcode("xs4_3V"),
+            Err(ParseError::InvalidToken {
+                row: 0,
+                column: 5,
+                token: "V".to_string(),
+            })
         );
+        assert!(Board::from_apgcode("xs2_1Y31").is_err());
+    }
 
-        let coord = Coord::new(2, 2);
+    #[test]
+    fn stamp_text_clips_oversized_offsets_on_dead_boundary() {
+        let mut board = Board::new(9, 7);
+        board.set_boundary(Boundary::Dead);
 
-        assert_eq!(board.get_live_neighbours_of(&coord), 3);
+        board.stamp_text("A", Coord::new(usize::MAX, 0), &Font::default());
+        board.stamp_text("AB\nCD", Coord::new(0, usize::MAX - 3), &Font::default());
+
+        assert!(board.is_empty());
     }
 }
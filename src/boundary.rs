@@ -0,0 +1,45 @@
+/// How the edges of a board behave when looking up the neighbours of a cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Boundary {
+    /// Opposite edges are joined, so patterns leaving one side come back on the other
+    #[default]
+    Toroidal,
+    /// Everything outside the board is permanently dead, so patterns leaving the board are lost
+    Dead,
+    /// Opposite edges are joined, but the left and right edges with a twist, so patterns leaving
+    /// one side come back on the other side upside down. Patterns leaving the top or bottom come
+    /// back as on a torus
+    KleinBottle,
+    /// Like [`Boundary::KleinBottle`], but with the twist on the top and bottom edges, so patterns
+    /// leaving the top or bottom come back mirrored. This is a Klein bottle turned a quarter turn
+    VerticalKleinBottle,
+    /// Opposite edges are joined, both pairs with a twist, so patterns leaving the left or right
+    /// side come back upside down, and patterns leaving the top or bottom come back mirrored
+    ProjectivePlane,
+}
+
+impl Boundary {
+    /// Checks whether cells wrapping around the left or right edge are mirrored vertically
+    pub(crate) fn mirrors_horizontal_wrap(self) -> bool {
+        matches!(self, Boundary::KleinBottle | Boundary::ProjectivePlane)
+    }
+
+    /// Checks whether cells wrapping around the top or bottom edge are mirrored horizontally
+    pub(crate) fn mirrors_vertical_wrap(self) -> bool {
+        matches!(
+            self,
+            Boundary::VerticalKleinBottle | Boundary::ProjectivePlane
+        )
+    }
+
+    /// Gets the boundary of a board turned a quarter turn, which moves the twist of a Klein bottle
+    /// to the other pair of edges
+    pub(crate) fn rotated(self) -> Boundary {
+        match self {
+            Boundary::KleinBottle => Boundary::VerticalKleinBottle,
+            Boundary::VerticalKleinBottle => Boundary::KleinBottle,
+            boundary => boundary,
+        }
+    }
+}
@@ -1,7 +1,8 @@
 use std::fmt;
 
 /// Possible states for a cell
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CellState {
     /// A cell which is alive
     Alive,
@@ -0,0 +1,12 @@
+/// What happened to a cell between two generations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellTransition {
+    /// The cell was dead and became alive
+    Born,
+    /// The cell was alive and stayed alive
+    Survived,
+    /// The cell was alive and became dead
+    Died,
+    /// The cell was dead and stayed dead
+    StayedDead,
+}
@@ -0,0 +1,22 @@
+/// Colours used when rendering a board to an image, as RGB triples
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Colors {
+    /// Colour of live cells
+    pub alive: [u8; 3],
+    /// Colour of dead cells
+    pub dead: [u8; 3],
+}
+
+impl Colors {
+    /// Creates a new set of colours
+    pub fn new(alive: [u8; 3], dead: [u8; 3]) -> Colors {
+        Colors { alive, dead }
+    }
+}
+
+impl Default for Colors {
+    /// Black live cells on a white background
+    fn default() -> Colors {
+        Colors::new([0, 0, 0], [255, 255, 255])
+    }
+}
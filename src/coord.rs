@@ -1,5 +1,20 @@
+use std::ops::Add;
+
+/// Offsets to the eight neighbours of a cell
+pub(crate) const NEIGHBOUR_OFFSETS: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
 /// Coordinates on a rectangular Game of Life board
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Coord {
     pub x: usize,
     pub y: usize,
@@ -10,6 +25,73 @@ impl Coord {
     pub fn new(x: usize, y: usize) -> Coord {
         Coord { x, y }
     }
+
+    /// Moves the coordinate by the given offsets, or returns `None` if the result would not fit in
+    /// a coordinate
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::Coord;
+    ///
+    /// let coord = Coord::new(3, 1);
+    ///
+    /// assert_eq!(coord.offset(-1, 2), Some(Coord::new(2, 3)));
+    /// assert_eq!(coord.offset(0, -2), None);
+    /// ```
+    pub fn offset(&self, dx: isize, dy: isize) -> Option<Coord> {
+        Some(Coord::new(
+            self.x.checked_add_signed(dx)?,
+            self.y.checked_add_signed(dy)?,
+        ))
+    }
+
+    /// Moves the coordinate by the given offsets on a board of the given size, wrapping around the
+    /// edges like on a torus
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::Coord;
+    ///
+    /// let coord = Coord::new(0, 4);
+    ///
+    /// assert_eq!(coord.translate_wrapping(-1, 1, 5, 5), Coord::new(4, 0));
+    /// assert_eq!(coord.translate_wrapping(12, -9, 5, 5), Coord::new(2, 0));
+    /// ```
+    ///
+    /// # Panics
+    /// If width or height is 0
+    pub fn translate_wrapping(&self, dx: isize, dy: isize, width: usize, height: usize) -> Coord {
+        Coord::new(
+            Coord::wrap(self.x, dx, width),
+            Coord::wrap(self.y, dy, height),
+        )
+    }
+
+    /// Moves a position along an axis of the given length, wrapping around at the ends
+    fn wrap(position: usize, delta: isize, len: usize) -> usize {
+        assert!(len > 0, "Cannot wrap around an axis of length 0");
+
+        let step = delta.unsigned_abs() % len;
+        let forwards = if delta < 0 { len - step } else { step };
+
+        (position % len + forwards) % len
+    }
+}
+
+impl Add for Coord {
+    type Output = Coord;
+
+    /// Adds the components of two coordinates
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::Coord;
+    ///
+    /// assert_eq!(Coord::new(1, 2) + Coord::new(3, 4), Coord::new(4, 6));
+    /// ```
+    fn add(self, other: Coord) -> Coord {
+        Coord::new(self.x + other.x, self.y + other.y)
+    }
 }
 
 #[cfg(test)]
@@ -25,4 +107,24 @@ mod test {
         assert_eq!(coord.x, x);
         assert_eq!(coord.y, y);
     }
+
+    #[test]
+    fn offset_past_usize_max_is_none() {
+        assert_eq!(Coord::new(usize::MAX, 0).offset(1, 0), None);
+        assert_eq!(
+            Coord::new(usize::MAX - 1, 5).offset(1, -5),
+            Some(Coord::new(usize::MAX, 0))
+        );
+    }
+
+    #[test]
+    fn translate_wrapping_handles_extreme_offsets() {
+        let coord = Coord::new(2, 3);
+
+        assert_eq!(coord.translate_wrapping(0, 0, 4, 4), coord);
+        assert_eq!(
+            coord.translate_wrapping(isize::MIN, isize::MAX, 4, 4),
+            Coord::new(2, 2)
+        );
+    }
 }
@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+
+/// A bitmap font for writing text onto a board. Each glyph is a grid of pixels, where the live
+/// pixels become live cells
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Font {
+    /// Width of every glyph
+    width: usize,
+    /// Height of every glyph
+    height: usize,
+    /// The pixels of each glyph, row by row
+    glyphs: HashMap<char, Vec<Vec<bool>>>,
+}
+
+impl Font {
+    /// Creates a new font without any glyphs
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::Font;
+    ///
+    /// let font = Font::new(5, 7);
+    ///
+    /// assert_eq!(font.width(), 5);
+    /// assert_eq!(font.height(), 7);
+    /// ```
+    ///
+    /// # Panics
+    /// If width or height is 0
+    pub fn new(width: usize, height: usize) -> Font {
+        if width == 0 || height == 0 {
+            panic!("Width and height must be at least 1");
+        }
+
+        Font {
+            width,
+            height,
+            glyphs: HashMap::new(),
+        }
+    }
+
+    /// The width of every glyph in the font
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The height of every glyph in the font
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Adds a glyph to the font, replacing any existing glyph for the character. Each row is a
+    /// string where `#` is a live pixel and any other character is a dead pixel
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::Font;
+    ///
+    /// let mut font = Font::new(2, 2);
+    /// font.add_glyph('/', &[
+    ///     "_#",
+    ///     "#_",
+    /// ]);
+    ///
+    /// assert_eq!(font.glyph('/'), Some(&vec![vec![false, true], vec![true, false]]));
+    /// ```
+    ///
+    /// # Panics
+    /// If the number of rows or the length of a row does not match the font's dimensions
+    pub fn add_glyph(&mut self, c: char, rows: &[&str]) {
+        if rows.len() != self.height || rows.iter().any(|row| row.chars().count() != self.width) {
+            panic!(
+                "Glyph for '{}' must be {} by {} pixels",
+                c, self.width, self.height
+            );
+        }
+
+        let pixels = rows
+            .iter()
+            .map(|row| row.chars().map(|pixel| pixel == '#').collect())
+            .collect();
+
+        self.glyphs.insert(c, pixels);
+    }
+
+    /// Gets the pixels of the glyph for a character, row by row. Lowercase letters fall back to
+    /// the uppercase glyph if the font has no glyph for them
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::Font;
+    ///
+    /// let font = Font::default();
+    ///
+    /// assert!(font.glyph('A').is_some());
+    /// assert_eq!(font.glyph('a'), font.glyph('A'));
+    /// assert_eq!(font.glyph('€'), None);
+    /// ```
+    pub fn glyph(&self, c: char) -> Option<&Vec<Vec<bool>>> {
+        self.glyphs
+            .get(&c)
+            .or_else(|| self.glyphs.get(&c.to_ascii_uppercase()))
+    }
+}
+
+impl Default for Font {
+    /// A 3 by 5 pixel font with uppercase letters, digits, space and some punctuation
+    fn default() -> Font {
+        let mut font = Font::new(3, 5);
+
+        let glyphs: [(char, [&str; 5]); 42] = [
+            ('A', ["_#_", "#_#", "###", "#_#", "#_#"]),
+            ('B', ["##_", "#_#", "##_", "#_#", "##_"]),
+            ('C', ["_##", "#__", "#__", "#__", "_##"]),
+            ('D', ["##_", "#_#", "#_#", "#_#", "##_"]),
+            ('E', ["###", "#__", "##_", "#__", "###"]),
+            ('F', ["###", "#__", "##_", "#__", "#__"]),
+            ('G', ["_##", "#__", "#_#", "#_#", "_##"]),
+            ('H', ["#_#", "#_#", "###", "#_#", "#_#"]),
+            ('I', ["###", "_#_", "_#_", "_#_", "###"]),
+            ('J', ["__#", "__#", "__#", "#_#", "_#_"]),
+            ('K', ["#_#", "#_#", "##_", "#_#", "#_#"]),
+            ('L', ["#__", "#__", "#__", "#__", "###"]),
+            ('M', ["#_#", "###", "###", "#_#", "#_#"]),
+            ('N', ["##_", "#_#", "#_#", "#_#", "#_#"]),
+            ('O', ["_#_", "#_#", "#_#", "#_#", "_#_"]),
+            ('P', ["##_", "#_#", "##_", "#__", "#__"]),
+            ('Q', ["_#_", "#_#", "#_#", "##_", "_##"]),
+            ('R', ["##_", "#_#", "##_", "#_#", "#_#"]),
+            ('S', ["_##", "#__", "_#_", "__#", "##_"]),
+            ('T', ["###", "_#_", "_#_", "_#_", "_#_"]),
+            ('U', ["#_#", "#_#", "#_#", "#_#", "###"]),
+            ('V', ["#_#", "#_#", "#_#", "#_#", "_#_"]),
+            ('W', ["#_#", "#_#", "###", "###", "#_#"]),
+            ('X', ["#_#", "#_#", "_#_", "#_#", "#_#"]),
+            ('Y', ["#_#", "#_#", "_#_", "_#_", "_#_"]),
+            ('Z', ["###", "__#", "_#_", "#__", "###"]),
+            ('0', ["###", "#_#", "#_#", "#_#", "###"]),
+            ('1', ["_#_", "##_", "_#_", "_#_", "###"]),
+            ('2', ["##_", "__#", "_#_", "#__", "###"]),
+            ('3', ["##_", "__#", "_#_", "__#", "##_"]),
+            ('4', ["#_#", "#_#", "###", "__#", "__#"]),
+            ('5', ["###", "#__", "##_", "__#", "##_"]),
+            ('6', ["_##", "#__", "###", "#_#", "###"]),
+            ('7', ["###", "__#", "_#_", "_#_", "_#_"]),
+            ('8', ["###", "#_#", "###", "#_#", "###"]),
+            ('9', ["###", "#_#", "###", "__#", "##_"]),
+            (' ', ["___", "___", "___", "___", "___"]),
+            ('!', ["_#_", "_#_", "_#_", "___", "_#_"]),
+            ('?', ["##_", "__#", "_#_", "___", "_#_"]),
+            ('.', ["___", "___", "___", "___", "_#_"]),
+            (':', ["___", "_#_", "___", "_#_", "___"]),
+            ('-', ["___", "___", "###", "___", "___"]),
+        ];
+
+        for (c, rows) in glyphs.iter() {
+            font.add_glyph(*c, rows);
+        }
+
+        font
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[should_panic]
+    fn creating_font_with_0_width_panics() {
+        Font::new(0, 5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn adding_glyph_with_wrong_dimensions_panics() {
+        let mut font = Font::new(3, 5);
+
+        font.add_glyph('x', &["#_#", "_#_", "#_#"]);
+    }
+
+    #[test]
+    fn default_font_has_letters_and_digits() {
+        let font = Font::default();
+
+        assert!(('A'..='Z').all(|c| font.glyph(c).is_some()));
+        assert!(('0'..='9').all(|c| font.glyph(c).is_some()));
+    }
+}
@@ -1,13 +1,59 @@
 use crate::board::Board;
 use crate::cell_state::CellState;
+#[cfg(feature = "gif")]
+use crate::colors::Colors;
 use crate::coord::Coord;
 use crate::rules::Rules;
+use crate::velocity::Velocity;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet, VecDeque};
+#[cfg(feature = "gif")]
+use std::fs::File;
+#[cfg(feature = "gif")]
+use std::io::BufWriter;
+use std::io::{self, Write};
+#[cfg(feature = "gif")]
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Maximum number of generations `Game::ash_period_histogram` waits for the game to settle
+const MAX_SETTLE_GENS: usize = 10_000;
 
 /// A life-like game
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Game {
     board: Board,
     rules: Rules,
+    /// Number of generations the game has been advanced
+    generation: usize,
+    /// Cells which are revived after every generation, so they never die
+    #[cfg_attr(feature = "serde", serde(default))]
+    immortal: HashSet<Coord>,
+    /// Number of generations after which live cells die whatever their neighbours, if any
+    #[cfg_attr(feature = "serde", serde(default))]
+    max_age: Option<u8>,
+    /// Number of generations each live cell has stayed alive, only tracked with a maximum age.
+    /// Live cells missing from the map count as newborn
+    #[cfg_attr(feature = "serde", serde(default, with = "ages_serde"))]
+    ages: HashMap<Coord, u8>,
+}
+
+/// Serializes the ages of cells as a list of pairs, as coordinates cannot be keys in most formats
+#[cfg(feature = "serde")]
+mod ages_serde {
+    use crate::coord::Coord;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::collections::HashMap;
+
+    pub fn serialize<S: Serializer>(ages: &HashMap<Coord, u8>, s: S) -> Result<S::Ok, S::Error> {
+        s.collect_seq(ages)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<HashMap<Coord, u8>, D::Error> {
+        Vec::<(Coord, u8)>::deserialize(d).map(|ages| ages.into_iter().collect())
+    }
 }
 
 impl Game {
@@ -26,7 +72,14 @@ impl Game {
     /// );
     /// ```
     pub fn new(board: Board, rules: Rules) -> Game {
-        Game { board, rules }
+        Game {
+            board,
+            rules,
+            generation: 0,
+            immortal: HashSet::new(),
+            max_age: None,
+            ages: HashMap::new(),
+        }
     }
 
     /// Gets a reference to the game's board
@@ -69,6 +122,31 @@ impl Game {
         &self.rules
     }
 
+    /// Gets the number of generations the game has been advanced since it was created. Changing
+    /// the board by hand does not count as a generation
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Game, Board, rules};
+    ///
+    /// let mut game = Game::new(
+    ///     Board::from_str(&vec![
+    ///         "#_",
+    ///         "_#",
+    ///     ].join("\n"), '#'),
+    ///     rules::conways()
+    /// );
+    ///
+    /// assert_eq!(game.generation(), 0);
+    ///
+    /// game.advance_to_next_gen();
+    ///
+    /// assert_eq!(game.generation(), 1);
+    /// ```
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
     /// Gets a mutable reference to the game's board
     ///
     /// # Examples
@@ -91,7 +169,9 @@ impl Game {
         &mut self.board
     }
 
-    /// Makes a map of all coordinates which will be toggled in the next iteration of this game
+    /// Makes a map of all coordinates which will be toggled in the next iteration of this game.
+    /// With the `rayon` feature, the cells are checked in parallel, giving the same coordinates in
+    /// the same order as checking them one by one
     ///
     /// # Examples
     /// ```
@@ -119,17 +199,41 @@ impl Game {
     /// ```
     pub fn next_gen_toggles(&self) -> Vec<&Coord> {
         let board = self.board();
+        let counts = board.live_neighbour_counts();
 
-        board
-            .cell_coords()
-            .iter()
-            .map(|c| (c, board.get_cell_state(c), board.get_live_neighbours_of(c)))
-            .filter(|(_, cell_state, live_neighbours)| match cell_state {
-                CellState::Alive => !self.rules().survives(*live_neighbours),
-                CellState::Dead => self.rules().birthed(*live_neighbours),
-            })
-            .map(|(c, _, _)| c)
-            .collect()
+        let toggles = |(c, live_neighbours): &(&Coord, &u8)| match board.get_cell_state(c) {
+            CellState::Alive => !self.rules().survives(**live_neighbours),
+            CellState::Dead => self.rules().birthed(**live_neighbours),
+        };
+
+        #[cfg(not(feature = "rayon"))]
+        let cells = board.cell_coords().iter().zip(&counts);
+        #[cfg(feature = "rayon")]
+        let cells = board.cell_coords().par_iter().zip(&counts);
+
+        let toggled: Vec<&Coord> = cells.filter(toggles).map(|(c, _)| c).collect();
+
+        // Guards against the parallel path ever disagreeing with checking the cells one by one
+        #[cfg(all(feature = "rayon", debug_assertions))]
+        {
+            let mut serial: Vec<&Coord> = board
+                .cell_coords()
+                .iter()
+                .zip(&counts)
+                .filter(toggles)
+                .map(|(c, _)| c)
+                .collect();
+            let mut parallel = toggled.clone();
+            serial.sort_by_key(|c| (c.y, c.x));
+            parallel.sort_by_key(|c| (c.y, c.x));
+
+            debug_assert_eq!(
+                parallel, serial,
+                "Parallel toggles differ from serial toggles"
+            );
+        }
+
+        toggled
     }
 
     /// Applies a list of coordinates to toggle on the game's board
@@ -167,7 +271,33 @@ impl Game {
         }
     }
 
-    /// Advances the game to the next generation
+    /// Applies a list of coordinates to toggle on the game's board, toggling each coordinate at
+    /// most once even if it appears several times in the list. With [`Game::apply_toggles`],
+    /// duplicates toggle the cell back and forth
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Game, Board, Coord, CellState, rules};
+    ///
+    /// let mut game = Game::new(Board::new(3, 3), rules::conways());
+    ///
+    /// game.apply_toggles_deduped(&[
+    ///     Coord::new(1, 1),
+    ///     Coord::new(1, 1),
+    /// ]);
+    ///
+    /// assert_eq!(game.board().get_cell_state(&Coord::new(1, 1)), &CellState::Alive);
+    /// ```
+    pub fn apply_toggles_deduped(&mut self, toggles: &[Coord]) {
+        let toggles: HashSet<&Coord> = toggles.iter().collect();
+
+        for coord in toggles {
+            self.board.toggle_cell(coord);
+        }
+    }
+
+    /// Advances the game to the next generation. A board with no live cells is left alone without
+    /// looking at its neighbourhoods, unless the rules give birth to cells with no live neighbours
     ///
     /// # Examples
     /// ```
@@ -197,6 +327,41 @@ impl Game {
     /// assert_eq!(game.board().to_str('#', '_'), expected_board_str);
     /// ```
     pub fn advance_to_next_gen(&mut self) {
+        self.step();
+    }
+
+    /// Advances the game to the next generation like [`Game::advance_to_next_gen`], telling
+    /// whether any cell changed. Once this returns `false`, the board has reached a fixed point
+    /// and will never change again
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Game, Board, rules};
+    ///
+    /// let mut game = Game::new(
+    ///     Board::from_str(&vec![
+    ///         "_____",
+    ///         "_##__",
+    ///         "_#___",
+    ///         "_____",
+    ///     ].join("\n"), '#'),
+    ///     rules::conways()
+    /// );
+    ///
+    /// // The missing corner of the block is born
+    /// assert!(game.step());
+    /// assert!(!game.step());
+    /// assert_eq!(game.generation(), 2);
+    /// ```
+    pub fn step(&mut self) -> bool {
+        self.generation += 1;
+
+        // A board without live cells stays empty unless cells can be born with no neighbours, so
+        // there is no need to count the neighbours of every cell
+        if !self.rules.birthed(0) && self.board.is_empty() && self.immortal.is_empty() {
+            return false;
+        }
+
         let toggles = self.next_gen_toggles();
 
         // XXX `toggles` contains references to `self`, so `self.apply_toggles` complains about
@@ -209,8 +374,2112 @@ impl Game {
             let c = coord.clone();
             coords.push(c);
         }
-        let toggles: Vec<&Coord> = coords.iter().map(|c| c).collect();
+        let expired = self.expired_cells(&coords);
+        let toggles: Vec<&Coord> = coords
+            .iter()
+            .chain(&expired)
+            .filter(|c| !self.keeps_alive(c))
+            .collect();
 
         self.apply_toggles(&toggles);
+        let revived = self.revive_immortal();
+        self.age_cells(&coords);
+
+        !toggles.is_empty() || revived
+    }
+
+    /// Advances the game the given number of generations. Stops simulating early if the board
+    /// dies out, as long as the rules do not give birth to cells with no live neighbours. The
+    /// generation number is advanced by the full amount either way
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Game, Board, rules};
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "_____",
+    ///     "__#__",
+    ///     "__#__",
+    ///     "__#__",
+    ///     "_____",
+    /// ].join("\n"), '#');
+    ///
+    /// let mut game = Game::new(board.clone(), rules::conways());
+    ///
+    /// game.advance_generations(4);
+    ///
+    /// assert_eq!(game.board(), &board);
+    /// ```
+    pub fn advance_generations(&mut self, n: usize) {
+        for advanced in 0..n {
+            if !self.rules.birthed(0) && self.board.is_empty() && self.immortal.is_empty() {
+                self.generation += n - advanced;
+                return;
+            }
+
+            self.advance_to_next_gen();
+        }
+    }
+
+    /// Advances the game to the next generation like [`Game::advance_to_next_gen`], but only
+    /// looks at the neighbourhoods of live cells. Each live cell adds to the neighbour count of
+    /// the cells around it, so cells far away from any live cell are never visited. This is much
+    /// faster on large, sparse boards. Rules which give birth to cells with no live neighbours
+    /// need every cell to be checked, so those fall back to the full scan
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Game, Board, rules};
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "_____",
+    ///     "__#__",
+    ///     "__#__",
+    ///     "__#__",
+    ///     "_____",
+    /// ].join("\n"), '#');
+    ///
+    /// let mut game = Game::new(board.clone(), rules::conways());
+    /// let mut sparse_game = Game::new(board, rules::conways());
+    ///
+    /// game.advance_to_next_gen();
+    /// sparse_game.advance_to_next_gen_sparse();
+    ///
+    /// assert_eq!(game.board(), sparse_game.board());
+    /// ```
+    pub fn advance_to_next_gen_sparse(&mut self) {
+        if self.rules.birthed(0) {
+            self.advance_to_next_gen();
+            return;
+        }
+
+        self.generation += 1;
+
+        let board = &self.board;
+        let live_cells: Vec<&Coord> = board.live_cells().collect();
+
+        let mut neighbour_counts: HashMap<Coord, u8> = HashMap::new();
+        for coord in &live_cells {
+            for neighbour in board.get_neighbour_coords(coord) {
+                *neighbour_counts.entry(neighbour).or_insert(0) += 1;
+            }
+        }
+
+        // Live cells missing from the map have no live neighbours
+        let deaths = live_cells
+            .iter()
+            .filter(|c| {
+                !self
+                    .rules
+                    .survives(neighbour_counts.get(c).copied().unwrap_or(0))
+            })
+            .map(|c| (*c).clone());
+
+        let births = neighbour_counts
+            .iter()
+            .filter(|(c, count)| {
+                board.get_cell_state(c) == &CellState::Dead && self.rules.birthed(**count)
+            })
+            .map(|(c, _)| c.clone());
+
+        let mut toggles: Vec<Coord> = deaths.chain(births).collect();
+        toggles.extend(self.expired_cells(&toggles));
+        toggles.retain(|c| !self.keeps_alive(c));
+
+        for coord in &toggles {
+            self.board.toggle_cell(coord);
+        }
+        self.revive_immortal();
+        self.age_cells(&toggles);
+    }
+
+    /// Advances only the cells within the rectangle with the given top left corner and size to the
+    /// next generation, leaving the rest of the board frozen. Cells at the edges of the rectangle
+    /// see their neighbours outside it as usual. Parts of the rectangle outside the board are
+    /// ignored. As only part of the board is advanced, the generation number is left alone
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Game, Board, Coord, rules};
+    ///
+    /// let mut game = Game::new(Board::from_str(&vec![
+    ///     "_________",
+    ///     "__#___#__",
+    ///     "__#___#__",
+    ///     "__#___#__",
+    ///     "_________",
+    /// ].join("\n"), '#'), rules::conways());
+    ///
+    /// game.advance_region(Coord::new(0, 0), 5, 5);
+    ///
+    /// assert_eq!(game.board().to_str('#', '_'), vec![
+    ///     "_________",
+    ///     "______#__",
+    ///     "_###__#__",
+    ///     "______#__",
+    ///     "_________",
+    /// ].join("\n"));
+    /// ```
+    pub fn advance_region(&mut self, top_left: Coord, width: usize, height: usize) {
+        let right = top_left.x.saturating_add(width).min(self.board.width());
+        let bottom = top_left.y.saturating_add(height).min(self.board.height());
+
+        let mut toggles = Vec::new();
+        for y in top_left.y..bottom {
+            for x in top_left.x..right {
+                let coord = Coord::new(x, y);
+                let live_neighbours = self.board.get_live_neighbours_of(&coord);
+
+                let toggle = match self.board.get_cell_state(&coord) {
+                    CellState::Alive => !self.rules.survives(live_neighbours),
+                    CellState::Dead => self.rules.birthed(live_neighbours),
+                };
+
+                let expired = !toggle && self.is_expired(&coord);
+
+                if (toggle || expired) && !self.keeps_alive(&coord) {
+                    toggles.push(coord);
+                }
+            }
+        }
+
+        for coord in &toggles {
+            self.board.toggle_cell(coord);
+        }
+        self.revive_immortal();
+        self.age_cells(&toggles);
+    }
+
+    /// Marks cells as immortal, replacing any earlier immortal cells. Immortal cells are revived
+    /// right away and after every generation, so they never die whatever the rules say. They are
+    /// ordinary live cells otherwise, and count as neighbours for births and survival of the cells
+    /// around them
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Game, Board, Coord, CellState, rules};
+    ///
+    /// let mut game = Game::new(Board::new(5, 5), rules::conways());
+    ///
+    /// game.set_immortal(&[Coord::new(2, 2)]);
+    /// game.advance_generations(3);
+    ///
+    /// assert_eq!(game.board().get_cell_state(&Coord::new(2, 2)), &CellState::Alive);
+    /// ```
+    ///
+    /// # Panics
+    /// If any of the coordinates are outside the board
+    pub fn set_immortal(&mut self, coords: &[Coord]) {
+        self.immortal = coords.iter().cloned().collect();
+        self.revive_immortal();
+    }
+
+    /// Sets the number of generations a cell may stay alive before it dies whatever its
+    /// neighbours, or `None` to let cells live forever. This composes with the rules: a cell which
+    /// has been alive for `max_age` generations dies on the next step, but may be born again
+    /// afterwards. Cells alive when the maximum age is set start out with an age of 0. Immortal
+    /// cells never age out
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Game, Board, rules};
+    ///
+    /// let block = Board::from_str(&vec![
+    ///     "____",
+    ///     "_##_",
+    ///     "_##_",
+    ///     "____",
+    /// ].join("\n"), '#');
+    ///
+    /// let mut game = Game::new(block, rules::conways());
+    /// game.set_max_age(Some(2));
+    ///
+    /// game.advance_generations(1);
+    /// assert_eq!(game.board().population(), 4);
+    ///
+    /// game.advance_generations(1);
+    /// assert!(game.board().is_empty());
+    /// ```
+    pub fn set_max_age(&mut self, max_age: Option<u8>) {
+        self.max_age = max_age;
+        self.ages.clear();
+    }
+
+    /// Gets the number of generations a cell may stay alive, if limited
+    pub fn max_age(&self) -> Option<u8> {
+        self.max_age
+    }
+
+    /// Checks whether a live cell has been alive for the maximum age, and must die on this step
+    fn is_expired(&self, coord: &Coord) -> bool {
+        self.max_age.is_some_and(|max_age| {
+            let age = self.ages.get(coord).copied().unwrap_or(0);
+            self.board.get_cell_state(coord) == &CellState::Alive
+                && age.saturating_add(1) >= max_age
+        })
+    }
+
+    /// Finds the live cells which must die of old age on this step, leaving out those already
+    /// among the toggles
+    fn expired_cells(&self, toggles: &[Coord]) -> Vec<Coord> {
+        if self.max_age.is_none() {
+            return Vec::new();
+        }
+
+        let toggles: HashSet<&Coord> = toggles.iter().collect();
+        self.board
+            .live_cells()
+            .filter(|c| !toggles.contains(c) && self.is_expired(c))
+            .cloned()
+            .collect()
+    }
+
+    /// Ages the live cells by one generation after the toggles of a step have been applied. Cells
+    /// which were toggled and are alive now were just born, so they start at 0
+    fn age_cells(&mut self, toggles: &[Coord]) {
+        if self.max_age.is_none() {
+            return;
+        }
+
+        let toggled: HashSet<&Coord> = toggles.iter().collect();
+        let ages = self
+            .board
+            .live_cells()
+            .map(|c| {
+                let age = if toggled.contains(c) {
+                    0
+                } else {
+                    self.ages.get(c).copied().unwrap_or(0).saturating_add(1)
+                };
+                (c.clone(), age)
+            })
+            .collect();
+        self.ages = ages;
+    }
+
+    /// Checks whether a cell is an immortal cell which is alive, and must stay that way
+    fn keeps_alive(&self, coord: &Coord) -> bool {
+        self.immortal.contains(coord) && self.board.get_cell_state(coord) == &CellState::Alive
+    }
+
+    /// Revives all immortal cells, telling whether any of them were dead
+    fn revive_immortal(&mut self) -> bool {
+        let mut revived = false;
+
+        for coord in &self.immortal {
+            if self.board.get_cell_state(coord) == &CellState::Dead {
+                self.board.revive_cell(coord);
+                revived = true;
+            }
+        }
+
+        revived
+    }
+
+    /// Advances the game the given number of generations, folding an accumulator over the board
+    /// of each new generation. The closure gets the accumulator, the board and how many
+    /// generations have been advanced so far, starting at 1
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Game, Board, rules};
+    ///
+    /// let mut game = Game::new(
+    ///     Board::from_str(&vec![
+    ///         "_____",
+    ///         "__#__",
+    ///         "__#__",
+    ///         "__#__",
+    ///         "_____",
+    ///     ].join("\n"), '#'),
+    ///     rules::conways()
+    /// );
+    ///
+    /// let total_population = game.fold_gens(4, 0, |total, board, _| total + board.population());
+    ///
+    /// assert_eq!(total_population, 12);
+    /// ```
+    pub fn fold_gens<B, F: FnMut(B, &Board, usize) -> B>(
+        &mut self,
+        generations: usize,
+        init: B,
+        mut f: F,
+    ) -> B {
+        let mut acc = init;
+
+        for generation in 1..=generations {
+            self.advance_to_next_gen();
+            acc = f(acc, self.board(), generation);
+        }
+
+        acc
+    }
+
+    /// Detects the velocity of a spaceship by advancing the game until the pattern reappears,
+    /// possibly shifted, within the given number of generations. Patterns which reappear in the
+    /// same place, like still lifes and oscillators, get a velocity with no displacement. Returns
+    /// `None` if the board is empty or the pattern does not reappear in time. The game is
+    /// restored to its original state afterwards
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Game, Board, Velocity, rules};
+    ///
+    /// let mut game = Game::new(
+    ///     Board::from_str(&vec![
+    ///         "________",
+    ///         "__#_____",
+    ///         "___#____",
+    ///         "_###____",
+    ///         "________",
+    ///         "________",
+    ///         "________",
+    ///         "________",
+    ///     ].join("\n"), '#'),
+    ///     rules::conways()
+    /// );
+    ///
+    /// let velocity = game.velocity(10).unwrap();
+    ///
+    /// assert_eq!(velocity, Velocity::new(1, 1, 4));
+    /// assert_eq!(velocity.to_string(), "(1, 1)c/4");
+    /// ```
+    pub fn velocity(&mut self, max_gens: usize) -> Option<Velocity> {
+        let original = self.clone();
+        let (start, pattern) = self.board.toroidal_crop()?;
+
+        let mut velocity = None;
+        for generation in 1..=max_gens {
+            self.advance_to_next_gen();
+
+            match self.board.toroidal_crop() {
+                Some((position, current)) if current == pattern => {
+                    velocity = Some(Velocity::new(
+                        Game::wrapped_displacement(start.x, position.x, self.board.width()),
+                        Game::wrapped_displacement(start.y, position.y, self.board.height()),
+                        generation,
+                    ));
+                    break;
+                }
+                Some(_) => (),
+                None => break,
+            }
+        }
+
+        *self = original;
+
+        velocity
+    }
+
+    /// Finds when the pattern stops growing, by advancing the game until neither the width nor
+    /// the height of the bounding box of the live cells has exceeded its largest earlier value for
+    /// `window` generations. Returns the generation the largest dimensions were reached at, or
+    /// `None` if the pattern keeps growing for `max_gens` generations. Still lifes and
+    /// oscillators stop growing quickly, while guns and other growing patterns never do. The game
+    /// is restored to its original state afterwards
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Game, Board, rules};
+    ///
+    /// let mut game = Game::new(
+    ///     Board::from_str(&vec![
+    ///         "______",
+    ///         "______",
+    ///         "_###__",
+    ///         "__#___",
+    ///         "______",
+    ///         "______",
+    ///     ].join("\n"), '#'),
+    ///     rules::conways()
+    /// );
+    ///
+    /// // The T-tetromino grows for a few generations before becoming a stable traffic light
+    /// assert!(game.bounding_box_stabilization(4, 20).unwrap() > 0);
+    /// ```
+    pub fn bounding_box_stabilization(&mut self, window: usize, max_gens: usize) -> Option<usize> {
+        let original = self.clone();
+
+        let dimensions = |board: &Board| {
+            board
+                .toroidal_bounding_box()
+                .map(|(_, width, height)| (width, height))
+        };
+
+        let mut largest = dimensions(&self.board);
+        let mut stable_since = 0;
+        let mut stabilization = None;
+
+        for generation in 0..=max_gens {
+            if generation > 0 {
+                self.advance_to_next_gen();
+
+                let grown = match (largest, dimensions(&self.board)) {
+                    (Some((width, height)), Some((next_width, next_height))) => {
+                        (next_width > width || next_height > height)
+                            .then(|| (width.max(next_width), height.max(next_height)))
+                    }
+                    (None, next) => next,
+                    (_, None) => None,
+                };
+
+                if let Some(grown) = grown {
+                    largest = Some(grown);
+                    stable_since = generation;
+                }
+            }
+
+            if generation - stable_since >= window {
+                stabilization = Some(stable_since);
+                break;
+            }
+        }
+
+        *self = original;
+
+        stabilization
+    }
+
+    /// Compares how two games evolve, by advancing copies of both side by side. Returns the first
+    /// generation at which their boards differ, or `generations` if they stay identical
+    /// throughout. Useful for comparing rules on the same starting board
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Game, Board, rules::{self, Rules}};
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "_______",
+    ///     "_______",
+    ///     "__###__",
+    ///     "__#_#__",
+    ///     "___#___",
+    ///     "_______",
+    ///     "_______",
+    /// ].join("\n"), '#');
+    ///
+    /// let conways = Game::new(board.clone(), rules::conways());
+    /// let highlife = Game::new(board, Rules { b: vec![3, 6], s: vec![2, 3] });
+    ///
+    /// // The center cell has 6 live neighbours, so it is only born in HighLife
+    /// assert_eq!(Game::trajectory_divergence(&conways, &highlife, 10), 1);
+    /// ```
+    ///
+    /// # Panics
+    /// If the boards of the games have different dimensions
+    pub fn trajectory_divergence(a: &Game, b: &Game, generations: usize) -> usize {
+        if a.board.width() != b.board.width() || a.board.height() != b.board.height() {
+            panic!("The boards of the games must have the same dimensions");
+        }
+
+        let mut a = a.clone();
+        let mut b = b.clone();
+
+        for generation in 0..generations {
+            if a.board != b.board {
+                return generation;
+            }
+
+            a.advance_to_next_gen();
+            b.advance_to_next_gen();
+        }
+
+        generations
+    }
+
+    /// Finds the first generation at which the cell at the given coordinate is alive, by
+    /// advancing the game up to `max_gens` generations. Generation 0 is the current board.
+    /// Returns `None` if the cell does not come alive in time. The game is restored to its
+    /// original state afterwards
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Game, Board, Coord, rules};
+    ///
+    /// let mut game = Game::new(
+    ///     Board::from_str(&vec![
+    ///         "_____",
+    ///         "__#__",
+    ///         "__#__",
+    ///         "__#__",
+    ///         "_____",
+    ///     ].join("\n"), '#'),
+    ///     rules::conways()
+    /// );
+    ///
+    /// assert_eq!(game.first_alive_generation(Coord::new(2, 2), 5), Some(0));
+    /// assert_eq!(game.first_alive_generation(Coord::new(1, 2), 5), Some(1));
+    /// assert_eq!(game.first_alive_generation(Coord::new(0, 0), 5), None);
+    /// ```
+    pub fn first_alive_generation(&mut self, coord: Coord, max_gens: usize) -> Option<usize> {
+        let original = self.clone();
+
+        let mut first_alive = None;
+        for generation in 0..=max_gens {
+            if generation > 0 {
+                self.advance_to_next_gen();
+            }
+
+            if self.board.get_cell_state(&coord) == &CellState::Alive {
+                first_alive = Some(generation);
+                break;
+            }
+        }
+
+        *self = original;
+
+        first_alive
+    }
+
+    /// Finds the first generation at which more than `threshold` cells are alive, by advancing
+    /// the game up to `max_gens` generations. Generation 0 is the current board. Returns `None` if
+    /// the population stays at or below the threshold. The game is restored to its original state
+    /// afterwards
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Game, Board, rules};
+    ///
+    /// let mut game = Game::new(
+    ///     Board::from_str(&vec![
+    ///         "_____",
+    ///         "__#__",
+    ///         "__#__",
+    ///         "__#__",
+    ///         "_____",
+    ///     ].join("\n"), '#'),
+    ///     rules::conways()
+    /// );
+    ///
+    /// assert_eq!(game.first_population_over(2, 5), Some(0));
+    /// // A blinker never has more than three live cells
+    /// assert_eq!(game.first_population_over(3, 5), None);
+    /// ```
+    pub fn first_population_over(&mut self, threshold: usize, max_gens: usize) -> Option<usize> {
+        let original = self.clone();
+
+        let mut first_over = None;
+        for generation in 0..=max_gens {
+            if generation > 0 {
+                self.advance_to_next_gen();
+            }
+
+            if self.board.population() > threshold {
+                first_over = Some(generation);
+                break;
+            }
+        }
+
+        *self = original;
+
+        first_over
+    }
+
+    /// Finds the generation with the most live cells while advancing the game the given number of
+    /// generations. The current board is generation 0. Returns the generation and its population,
+    /// picking the earliest generation if several have the same population. The game is restored
+    /// to its original state afterwards
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Game, Board, rules};
+    ///
+    /// let mut game = Game::new(Board::from_str(&vec![
+    ///     "______",
+    ///     "______",
+    ///     "__##__",
+    ///     "__#___",
+    ///     "______",
+    ///     "______",
+    /// ].join("\n"), '#'), rules::conways());
+    ///
+    /// // The missing corner of the block is born in the first generation
+    /// assert_eq!(game.peak_population(5), (1, 4));
+    /// ```
+    pub fn peak_population(&mut self, generations: usize) -> (usize, usize) {
+        let original = self.clone();
+
+        let mut peak = (0, self.board.population());
+        for generation in 1..=generations {
+            self.advance_to_next_gen();
+
+            let population = self.board.population();
+            if population > peak.1 {
+                peak = (generation, population);
+            }
+        }
+
+        *self = original;
+
+        peak
+    }
+
+    /// Finds the first generation at which the board equals the target board, by advancing the
+    /// game up to `within` generations. The current board is generation 0 and is not compared, so
+    /// an oscillator returns to its current phase after one period. Returns `None` if the target
+    /// is not reached in time. The game is restored to its original state afterwards
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Game, Board, rules};
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "_____",
+    ///     "__#__",
+    ///     "__#__",
+    ///     "__#__",
+    ///     "_____",
+    /// ].join("\n"), '#');
+    ///
+    /// let mut game = Game::new(board.clone(), rules::conways());
+    ///
+    /// assert_eq!(game.returns_to(&board, 5), Some(2));
+    /// assert_eq!(game.returns_to(&board, 1), None);
+    /// ```
+    pub fn returns_to(&mut self, target: &Board, within: usize) -> Option<usize> {
+        let original = self.clone();
+
+        let mut returned = None;
+        for generation in 1..=within {
+            self.advance_to_next_gen();
+
+            if &self.board == target {
+                returned = Some(generation);
+                break;
+            }
+        }
+
+        *self = original;
+
+        returned
+    }
+
+    /// Checks whether the current board evolves into the target board in exactly one generation
+    /// under the given rules, which need not be the rules of the game. Only the board is
+    /// advanced, so immortal cells are not kept alive. The game itself is left untouched
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Game, Board, rules};
+    ///
+    /// let game = Game::new(Board::from_str(&vec![
+    ///     "_____",
+    ///     "__#__",
+    ///     "__#__",
+    ///     "__#__",
+    ///     "_____",
+    /// ].join("\n"), '#'), rules::conways());
+    ///
+    /// let horizontal = Board::from_str(&vec![
+    ///     "_____",
+    ///     "_____",
+    ///     "_###_",
+    ///     "_____",
+    ///     "_____",
+    /// ].join("\n"), '#');
+    ///
+    /// assert!(game.is_predecessor_of(&horizontal, &rules::conways()));
+    /// assert!(!game.is_predecessor_of(game.board(), &rules::conways()));
+    /// ```
+    pub fn is_predecessor_of(&self, target: &Board, rules: &Rules) -> bool {
+        &self.board.advanced(rules) == target
+    }
+
+    /// Finds the period of the current board, by advancing the game up to `max_period`
+    /// generations until the board comes back to its current state. A still life has period 1.
+    /// Returns `None` if the board does not recur in time, like for spaceships on a large board or
+    /// patterns which are still evolving. The game is restored to its original state afterwards
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Game, Board, rules};
+    ///
+    /// let mut blinker = Game::new(Board::from_str(&vec![
+    ///     "_____",
+    ///     "__#__",
+    ///     "__#__",
+    ///     "__#__",
+    ///     "_____",
+    /// ].join("\n"), '#'), rules::conways());
+    ///
+    /// assert_eq!(blinker.detect_cycle(10), Some(2));
+    /// assert_eq!(blinker.detect_cycle(1), None);
+    ///
+    /// let mut block = Game::new(Board::from_str(&vec![
+    ///     "____",
+    ///     "_##_",
+    ///     "_##_",
+    ///     "____",
+    /// ].join("\n"), '#'), rules::conways());
+    ///
+    /// assert_eq!(block.detect_cycle(10), Some(1));
+    /// ```
+    pub fn detect_cycle(&mut self, max_period: usize) -> Option<usize> {
+        let current = self.board.clone();
+
+        self.returns_to(&current, max_period)
+    }
+
+    /// Finds the cells whose state stays the same while advancing the game the given number of
+    /// generations, like the still parts of a pattern next to oscillating ones. Both live and dead
+    /// cells are included. The game is restored to its original state afterwards
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Game, Board, Coord, rules};
+    ///
+    /// let mut game = Game::new(Board::from_str(&vec![
+    ///     "_____",
+    ///     "__#__",
+    ///     "__#__",
+    ///     "__#__",
+    ///     "_____",
+    /// ].join("\n"), '#'), rules::conways());
+    ///
+    /// let stable = game.stable_cells(2);
+    ///
+    /// // The middle of the blinker stays alive, while its ends die and are born again
+    /// assert!(stable.contains(&Coord::new(2, 2)));
+    /// assert!(!stable.contains(&Coord::new(2, 1)));
+    /// assert!(!stable.contains(&Coord::new(1, 2)));
+    /// ```
+    pub fn stable_cells(&mut self, generations: usize) -> Vec<Coord> {
+        let original = self.clone();
+
+        let mut stable = vec![true; self.board.cell_coords().len()];
+        for _ in 0..generations {
+            self.advance_to_next_gen();
+
+            for (i, coord) in self.board.cell_coords().iter().enumerate() {
+                if self.board.get_cell_state(coord) != original.board.get_cell_state(coord) {
+                    stable[i] = false;
+                }
+            }
+        }
+
+        *self = original;
+
+        self.board
+            .cell_coords()
+            .iter()
+            .zip(stable)
+            .filter(|(_, stable)| *stable)
+            .map(|(coord, _)| coord.clone())
+            .collect()
+    }
+
+    /// Finds the box enclosing the live cells of every phase of an oscillator with the given
+    /// period, by advancing the game through one full period. Returns the top left corner, width
+    /// and height of the box. The game is restored to its original state afterwards
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Game, Board, Coord, rules};
+    ///
+    /// let mut game = Game::new(Board::from_str(&vec![
+    ///     "_____",
+    ///     "__#__",
+    ///     "__#__",
+    ///     "__#__",
+    ///     "_____",
+    /// ].join("\n"), '#'), rules::conways());
+    ///
+    /// assert_eq!(game.oscillator_bounding_box(2), (Coord::new(1, 1), 3, 3));
+    /// ```
+    ///
+    /// # Panics
+    /// If the period is 0, or if there are no live cells in any of the phases
+    pub fn oscillator_bounding_box(&mut self, period: usize) -> (Coord, usize, usize) {
+        assert!(period > 0, "Period must be at least 1");
+
+        let original = self.clone();
+
+        let mut bounds: Option<(Coord, Coord)> = None;
+        for phase in 0..period {
+            if phase > 0 {
+                self.advance_to_next_gen();
+            }
+
+            if let Some((min, max)) = self.board.live_bounds() {
+                bounds = Some(match bounds {
+                    None => (min, max),
+                    Some((union_min, union_max)) => (
+                        Coord::new(union_min.x.min(min.x), union_min.y.min(min.y)),
+                        Coord::new(union_max.x.max(max.x), union_max.y.max(max.y)),
+                    ),
+                });
+            }
+        }
+
+        *self = original;
+
+        let (min, max) = bounds.expect("Oscillator has no live cells");
+
+        (min.clone(), max.x - min.x + 1, max.y - min.y + 1)
+    }
+
+    /// Measures the heat of an oscillator with the given period, which is the mean number of cells
+    /// changing state per generation over one full period. Still lifes have a heat of 0. The game
+    /// is restored to its original state afterwards
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Game, Board, rules};
+    ///
+    /// let mut game = Game::new(Board::from_str(&vec![
+    ///     "_____",
+    ///     "__#__",
+    ///     "__#__",
+    ///     "__#__",
+    ///     "_____",
+    /// ].join("\n"), '#'), rules::conways());
+    ///
+    /// // The ends of the blinker die and two new cells are born each generation
+    /// assert_eq!(game.heat(2), 4.0);
+    /// ```
+    ///
+    /// # Panics
+    /// If the period is 0
+    pub fn heat(&mut self, period: usize) -> f64 {
+        assert!(period > 0, "Period must be at least 1");
+
+        let original = self.clone();
+
+        let mut toggled = 0;
+        for _ in 0..period {
+            let previous = self.board.clone();
+            self.advance_to_next_gen();
+            toggled += self.board.changes_since(&previous).len();
+        }
+
+        *self = original;
+
+        toggled as f64 / period as f64
+    }
+
+    /// Writes `generations` generations of the game as a stream of Run Length Encoded patterns,
+    /// starting with the current generation. Each pattern is trimmed to its live cells, has a
+    /// header with the given rules, and is followed by a blank line. The game is left at the
+    /// generation after the last one written
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Game, Board, rules};
+    ///
+    /// let mut game = Game::new(
+    ///     Board::from_str(&vec![
+    ///         "_____",
+    ///         "__#__",
+    ///         "__#__",
+    ///         "__#__",
+    ///         "_____",
+    ///     ].join("\n"), '#'),
+    ///     rules::conways()
+    /// );
+    ///
+    /// let mut out = Vec::new();
+    /// game.stream_rle(&mut out, 2, &rules::conways()).unwrap();
+    ///
+    /// assert_eq!(String::from_utf8(out).unwrap(), vec![
+    ///     "x = 1, y = 3, rule = B3/S23",
+    ///     "o$o$o!",
+    ///     "",
+    ///     "x = 3, y = 1, rule = B3/S23",
+    ///     "3o!",
+    ///     "",
+    ///     "",
+    /// ].join("\n"));
+    /// ```
+    ///
+    /// # Errors
+    /// If writing to the output fails
+    pub fn stream_rle<W: Write>(
+        &mut self,
+        out: &mut W,
+        generations: usize,
+        rule: &Rules,
+    ) -> io::Result<()> {
+        for _ in 0..generations {
+            writeln!(out, "{}\n", self.board.to_trimmed_rle(rule))?;
+            self.advance_to_next_gen();
+        }
+
+        Ok(())
+    }
+
+    /// Writes an animated GIF of the game to the given path, with one frame for the current
+    /// generation and one for each of the following `generations` generations. Each cell is drawn
+    /// as a square of `cell_size` by `cell_size` pixels, and each frame is shown for `delay_ms`
+    /// milliseconds, rounded down to the hundredths of a second GIF supports. The animation loops
+    /// forever. The game is left at the last generation
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use game_of_life::{Game, Board, Colors, rules};
+    /// use std::path::Path;
+    ///
+    /// let mut game = Game::new(Board::with_glider_stream(20, 20, 2, 6), rules::conways());
+    ///
+    /// game.to_gif(Path::new("gliders.gif"), 80, 4, 100, Colors::default()).unwrap();
+    /// ```
+    ///
+    /// # Errors
+    /// If the file cannot be written, or if the image would be wider or taller than 65535 pixels
+    #[cfg(feature = "gif")]
+    pub fn to_gif(
+        &mut self,
+        path: &Path,
+        generations: usize,
+        cell_size: u32,
+        delay_ms: u16,
+        colors: Colors,
+    ) -> io::Result<()> {
+        let too_large =
+            || io::Error::new(io::ErrorKind::InvalidInput, "Image is too large for GIF");
+        let width =
+            u16::try_from(self.board.width() as u64 * cell_size as u64).map_err(|_| too_large())?;
+        let height = u16::try_from(self.board.height() as u64 * cell_size as u64)
+            .map_err(|_| too_large())?;
+
+        // The palette has the dead colour at index 0 and the alive colour at index 1
+        let palette: Vec<u8> = colors
+            .dead
+            .iter()
+            .chain(colors.alive.iter())
+            .copied()
+            .collect();
+
+        let file = BufWriter::new(File::create(path)?);
+        let mut encoder =
+            gif::Encoder::new(file, width, height, &palette).map_err(io::Error::other)?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(io::Error::other)?;
+
+        for generation in 0..=generations {
+            if generation > 0 {
+                self.advance_to_next_gen();
+            }
+
+            let pixels: Vec<u8> = self
+                .board
+                .to_pixels(cell_size)
+                .into_iter()
+                .map(|pixel| match pixel {
+                    CellState::Alive => 1,
+                    CellState::Dead => 0,
+                })
+                .collect();
+
+            let mut frame = gif::Frame::from_indexed_pixels(width, height, pixels, None);
+            frame.delay = delay_ms / 10;
+            encoder.write_frame(&frame).map_err(io::Error::other)?;
+        }
+
+        Ok(())
+    }
+
+    /// Measures which way births are trending, by following the centroid of the cells born in each
+    /// of the next `window` generations. Returns the average movement of the centroid per
+    /// generation along the x and y axes. Movement across the edges of the board is counted the
+    /// short way around. Returns `(0.0, 0.0)` if there are fewer than two generations with births.
+    /// The game is restored to its original state afterwards
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Game, Board, rules};
+    ///
+    /// let mut game = Game::new(
+    ///     Board::from_str(&vec![
+    ///         "_#______",
+    ///         "__#_____",
+    ///         "###_____",
+    ///         "________",
+    ///         "________",
+    ///         "________",
+    ///         "________",
+    ///         "________",
+    ///     ].join("\n"), '#'),
+    ///     rules::conways()
+    /// );
+    ///
+    /// let (dx, dy) = game.activity_drift(9);
+    ///
+    /// // The glider moves one cell down and to the right every four generations
+    /// assert_eq!((dx, dy), (0.25, 0.25));
+    /// ```
+    pub fn activity_drift(&mut self, window: usize) -> (f64, f64) {
+        let original = self.clone();
+        let (width, height) = (self.board.width() as f64, self.board.height() as f64);
+
+        // Shortest distance along a wrapping axis
+        let wrapped = |distance: f64, len: f64| distance - len * (distance / len).round();
+
+        let mut centroids: Vec<(usize, f64, f64)> = Vec::new();
+        for generation in 0..window {
+            let births: Vec<&Coord> = self
+                .next_gen_toggles()
+                .into_iter()
+                .filter(|c| self.board.get_cell_state(c) == &CellState::Dead)
+                .collect();
+
+            // Average the positions relative to one of the births, so births on both sides of an
+            // edge are averaged the short way around
+            if let Some(reference) = births.first() {
+                let count = births.len() as f64;
+                let (x, y) = (reference.x as f64, reference.y as f64);
+                centroids.push((
+                    generation,
+                    x + births
+                        .iter()
+                        .map(|c| wrapped(c.x as f64 - x, width))
+                        .sum::<f64>()
+                        / count,
+                    y + births
+                        .iter()
+                        .map(|c| wrapped(c.y as f64 - y, height))
+                        .sum::<f64>()
+                        / count,
+                ));
+            }
+
+            self.advance_to_next_gen();
+        }
+
+        *self = original;
+
+        if centroids.len() < 2 {
+            return (0.0, 0.0);
+        }
+
+        let (dx, dy) = centroids.windows(2).fold((0.0, 0.0), |(dx, dy), pair| {
+            let ((_, x1, y1), (_, x2, y2)) = (pair[0], pair[1]);
+            (dx + wrapped(x2 - x1, width), dy + wrapped(y2 - y1, height))
+        });
+        let generations = (centroids[centroids.len() - 1].0 - centroids[0].0) as f64;
+
+        (dx / generations, dy / generations)
+    }
+
+    /// Finds the shortest signed distance from one index to another along a wrapping axis
+    fn wrapped_displacement(from: usize, to: usize, len: usize) -> isize {
+        let forward = (to + len - from) % len;
+
+        if forward > len / 2 {
+            forward as isize - len as isize
+        } else {
+            forward as isize
+        }
+    }
+
+    /// Runs the game until it settles into still lifes and oscillators, then tallies the period of
+    /// each separate object in the ash. The game counts as settled once the board repeats within
+    /// `max_period` generations, and is given up to 10 000 generations to do so. Objects which do
+    /// not repeat within `max_period` generations on their own, such as gliders, are left out.
+    /// Returns a map from period to the number of objects with that period, or `None` if the game
+    /// has not settled after 10 000 generations, like a glider on a large torus with a small
+    /// `max_period`. The game is left at the generation where it settled, or where it gave up
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Game, Board, rules};
+    ///
+    /// let mut game = Game::new(
+    ///     Board::from_str(&vec![
+    ///         "_________",
+    ///         "_##___#__",
+    ///         "_##___#__",
+    ///         "______#__",
+    ///         "_________",
+    ///     ].join("\n"), '#'),
+    ///     rules::conways()
+    /// );
+    ///
+    /// let histogram = game.ash_period_histogram(4).unwrap();
+    ///
+    /// assert_eq!(histogram.get(&1), Some(&1));
+    /// assert_eq!(histogram.get(&2), Some(&1));
+    /// ```
+    ///
+    /// # Panics
+    /// If max_period is 0
+    pub fn ash_period_histogram(&mut self, max_period: usize) -> Option<HashMap<usize, usize>> {
+        assert!(max_period > 0, "Max period must be at least 1");
+
+        let mut recent = VecDeque::with_capacity(max_period);
+        let mut settled = false;
+        for _ in 0..MAX_SETTLE_GENS {
+            if recent.contains(&self.board) {
+                settled = true;
+                break;
+            }
+
+            if recent.len() == max_period {
+                recent.pop_front();
+            }
+            recent.push_back(self.board.clone());
+
+            self.advance_to_next_gen();
+        }
+
+        if !settled {
+            return None;
+        }
+
+        let mut histogram = HashMap::new();
+        for component in self.board.live_components() {
+            let members: HashSet<Coord> = component.into_iter().collect();
+
+            let mut object = self.board.clone();
+            for coord in self.board.cell_coords() {
+                if !members.contains(coord) {
+                    object.kill_cell(coord);
+                }
+            }
+
+            let mut game = Game::new(object, self.rules.clone());
+            if let Some(period) = game.detect_cycle(max_period) {
+                *histogram.entry(period).or_insert(0) += 1;
+            }
+        }
+
+        Some(histogram)
+    }
+
+    /// Measures stepping throughput by advancing the game the given number of generations,
+    /// returning the number of generations per second. The game is left at the last generation
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Game, Board, rules};
+    ///
+    /// let mut game = Game::new(Board::with_glider_stream(50, 50, 5, 8), rules::conways());
+    ///
+    /// let generations_per_second = game.benchmark_steps(10);
+    ///
+    /// assert!(generations_per_second > 0.0);
+    /// ```
+    pub fn benchmark_steps(&mut self, generations: usize) -> f64 {
+        let start = Instant::now();
+
+        for _ in 0..generations {
+            self.advance_to_next_gen();
+        }
+
+        // Guard against a zero duration on very fast runs
+        let elapsed = start.elapsed().max(Duration::from_nanos(1));
+
+        generations as f64 / elapsed.as_secs_f64()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::boundary::Boundary;
+    use crate::neighbourhood::Neighbourhood;
+    use crate::rules;
+
+    fn vertical_blinker() -> Board {
+        Board::from_str(
+            &["_____", "__#__", "__#__", "__#__", "_____"].join("\n"),
+            '#',
+        )
+    }
+
+    #[test]
+    fn benchmark_steps_returns_positive_finite_number() {
+        let mut game = Game::new(Board::with_glider_stream(20, 20, 2, 6), rules::conways());
+
+        let generations_per_second = game.benchmark_steps(5);
+
+        assert!(generations_per_second > 0.0);
+        assert!(generations_per_second.is_finite());
+    }
+
+    #[test]
+    fn benchmark_steps_advances_the_game() {
+        let mut game = Game::new(vertical_blinker(), rules::conways());
+        let initial = game.board().clone();
+
+        game.benchmark_steps(1);
+
+        assert_ne!(game.board(), &initial);
+    }
+
+    #[test]
+    fn fold_gens_sums_populations_of_blinker() {
+        let mut game = Game::new(vertical_blinker(), rules::conways());
+
+        let total = game.fold_gens(3, 0, |total, board, _| total + board.population());
+
+        assert_eq!(total, 9);
+    }
+
+    #[test]
+    fn fold_gens_passes_generation_indices() {
+        let mut game = Game::new(vertical_blinker(), rules::conways());
+
+        let indices = game.fold_gens(3, Vec::new(), |mut indices, _, generation| {
+            indices.push(generation);
+            indices
+        });
+
+        assert_eq!(indices, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn velocity_of_glider_is_c_over_4_diagonal() {
+        let mut board = Board::new(12, 12);
+        let glider = crate::patterns::glider();
+        for coord in glider.cell_coords() {
+            if glider.get_cell_state(coord) == &CellState::Alive {
+                board.revive_cell(&Coord::new(coord.x + 4, coord.y + 4));
+            }
+        }
+        let mut game = Game::new(board, rules::conways());
+        let initial = game.clone();
+
+        let velocity = game.velocity(8).unwrap();
+
+        assert_eq!(velocity, Velocity::new(1, 1, 4));
+        assert!(velocity.is_diagonal());
+        assert_eq!(game, initial);
+    }
+
+    #[test]
+    fn velocity_of_glider_crossing_edge_is_detected() {
+        let board = Board::from_str(
+            &["______", "______", "______", "____#_", "_____#", "___###"].join("\n"),
+            '#',
+        );
+        let mut game = Game::new(board, rules::conways());
+
+        assert_eq!(game.velocity(8), Some(Velocity::new(1, 1, 4)));
+    }
+
+    #[test]
+    fn velocity_of_blinker_has_no_displacement() {
+        let mut game = Game::new(vertical_blinker(), rules::conways());
+
+        assert_eq!(game.velocity(4), Some(Velocity::new(0, 0, 2)));
+    }
+
+    #[test]
+    fn velocity_of_empty_board_is_none() {
+        let mut game = Game::new(Board::new(5, 5), rules::conways());
+
+        assert_eq!(game.velocity(4), None);
+    }
+
+    #[test]
+    fn bounding_box_of_block_stabilizes_immediately() {
+        let board = Board::from_str(&["____", "_##_", "_##_", "____"].join("\n"), '#');
+        let mut game = Game::new(board, rules::conways());
+
+        assert_eq!(game.bounding_box_stabilization(5, 10), Some(0));
+    }
+
+    #[test]
+    fn bounding_box_of_blinker_stabilizes_after_first_flip() {
+        // The blinker's box alternates between 1x3 and 3x1, and has covered 3x3 after one step
+        let mut game = Game::new(vertical_blinker(), rules::conways());
+
+        assert_eq!(game.bounding_box_stabilization(6, 10), Some(1));
+    }
+
+    #[test]
+    fn bounding_box_of_r_pentomino_keeps_growing() {
+        let mut board = Board::new(60, 60);
+        for (x, y) in [(30, 29), (31, 29), (29, 30), (30, 30), (30, 31)] {
+            board.revive_cell(&Coord::new(x, y));
+        }
+        let mut game = Game::new(board, rules::conways());
+
+        assert_eq!(game.bounding_box_stabilization(5, 30), None);
+    }
+
+    #[test]
+    fn bounding_box_stabilization_restores_game() {
+        let mut game = Game::new(vertical_blinker(), rules::conways());
+        let initial = game.clone();
+
+        game.bounding_box_stabilization(3, 5);
+
+        assert_eq!(game, initial);
+    }
+
+    #[test]
+    fn trajectory_divergence_of_conways_and_highlife() {
+        let board = Board::from_str(
+            &[
+                "_______", "_______", "__###__", "__#_#__", "___#___", "_______", "_______",
+            ]
+            .join("\n"),
+            '#',
+        );
+        let highlife = Rules {
+            b: vec![3, 6],
+            s: vec![2, 3],
+        };
+
+        let a = Game::new(board.clone(), rules::conways());
+        let b = Game::new(board, highlife);
+
+        assert_eq!(Game::trajectory_divergence(&a, &b, 10), 1);
+    }
+
+    #[test]
+    fn trajectory_divergence_of_identical_games_is_generations() {
+        let a = Game::new(vertical_blinker(), rules::conways());
+
+        assert_eq!(Game::trajectory_divergence(&a, &a.clone(), 6), 6);
+    }
+
+    #[test]
+    fn trajectory_divergence_of_different_boards_is_0() {
+        let a = Game::new(vertical_blinker(), rules::conways());
+        let b = Game::new(Board::new(5, 5), rules::conways());
+
+        assert_eq!(Game::trajectory_divergence(&a, &b, 6), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn trajectory_divergence_of_different_sizes_panics() {
+        let a = Game::new(Board::new(5, 5), rules::conways());
+        let b = Game::new(Board::new(5, 6), rules::conways());
+
+        Game::trajectory_divergence(&a, &b, 6);
+    }
+
+    #[test]
+    fn first_alive_generation_of_glider_reaching_cell() {
+        let mut board = Board::new(10, 10);
+        for (x, y) in [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)] {
+            board.revive_cell(&Coord::new(x, y));
+        }
+        let mut game = Game::new(board, rules::conways());
+        let initial = game.clone();
+
+        assert_eq!(game.first_alive_generation(Coord::new(4, 4), 20), Some(8));
+        assert_eq!(game.first_alive_generation(Coord::new(4, 4), 7), None);
+        assert_eq!(game, initial);
+    }
+
+    #[test]
+    fn apply_toggles_deduped_toggles_duplicates_once() {
+        let mut game = Game::new(Board::new(4, 4), rules::conways());
+        let coord = Coord::new(2, 1);
+        let other = Coord::new(0, 3);
+
+        game.apply_toggles_deduped(&[coord.clone(), other.clone(), coord.clone(), coord.clone()]);
+
+        assert_eq!(game.board().get_cell_state(&coord), &CellState::Alive);
+        assert_eq!(game.board().get_cell_state(&other), &CellState::Alive);
+    }
+
+    #[test]
+    fn advance_to_next_gen_leaves_empty_board_alone() {
+        let mut game = Game::new(Board::new(200, 200), rules::conways());
+
+        assert!(game.next_gen_toggles().is_empty());
+
+        for generation in 1..=100 {
+            assert!(!game.step());
+            assert_eq!(game.generation(), generation);
+        }
+
+        assert!(game.board().is_empty());
+    }
+
+    #[test]
+    fn advance_to_next_gen_births_on_empty_board_with_b0_rules() {
+        let rules = Rules {
+            b: vec![0],
+            s: vec![],
+        };
+        let mut game = Game::new(Board::new(3, 3), rules);
+
+        game.advance_to_next_gen();
+
+        assert_eq!(
+            game.board().to_str('#', '_'),
+            ["###", "###", "###"].join("\n")
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "gif")]
+    fn to_gif_writes_a_frame_per_generation() {
+        let path = std::env::temp_dir().join("game_of_life_to_gif_blinker.gif");
+        let mut game = Game::new(vertical_blinker(), rules::conways());
+
+        game.to_gif(&path, 3, 2, 50, Colors::default()).unwrap();
+
+        let mut decoder = gif::DecodeOptions::new()
+            .read_info(File::open(&path).unwrap())
+            .unwrap();
+        let mut frames = 0;
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!((frame.width, frame.height), (10, 10));
+            assert_eq!(frame.delay, 5);
+            frames += 1;
+        }
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(frames, 4);
+    }
+
+    #[test]
+    fn returns_to_finds_blinker_period_and_restores_state() {
+        let mut game = Game::new(vertical_blinker(), rules::conways());
+        let initial = game.clone();
+
+        assert_eq!(game.returns_to(&vertical_blinker(), 2), Some(2));
+        assert_eq!(game, initial);
+    }
+
+    #[test]
+    fn returns_to_finds_other_phase() {
+        let mut game = Game::new(vertical_blinker(), rules::conways());
+        let horizontal = Board::from_str(
+            &["_____", "_____", "_###_", "_____", "_____"].join("\n"),
+            '#',
+        );
+
+        assert_eq!(game.returns_to(&horizontal, 2), Some(1));
+        assert_eq!(game.returns_to(&Board::new(5, 5), 10), None);
+    }
+
+    #[test]
+    fn advance_to_next_gen_sparse_matches_full_scan_for_glider() {
+        let mut board = Board::new(20, 20);
+        for coord in [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)] {
+            board.revive_cell(&Coord::new(coord.0, coord.1));
+        }
+        let mut game = Game::new(board.clone(), rules::conways());
+        let mut sparse_game = Game::new(board, rules::conways());
+
+        // Long enough for the glider to wrap around the edges
+        for _ in 0..100 {
+            game.advance_to_next_gen();
+            sparse_game.advance_to_next_gen_sparse();
+
+            assert_eq!(game.board(), sparse_game.board());
+        }
+    }
+
+    #[test]
+    fn advance_to_next_gen_sparse_matches_full_scan_on_tiny_and_b0_boards() {
+        let b0 = Rules {
+            b: vec![0, 3],
+            s: vec![2, 3],
+        };
+
+        for rules in [rules::conways(), b0] {
+            for board in [
+                Board::from_str(&["#_", "_#"].join("\n"), '#'),
+                Board::with_glider_stream(12, 9, 2, 5),
+            ] {
+                let mut game = Game::new(board.clone(), rules.clone());
+                let mut sparse_game = Game::new(board, rules.clone());
+
+                for _ in 0..5 {
+                    game.advance_to_next_gen();
+                    sparse_game.advance_to_next_gen_sparse();
+
+                    assert_eq!(game.board(), sparse_game.board());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn first_population_over_finds_growth_of_r_pentomino() {
+        let mut board = Board::new(30, 30);
+        for (x, y) in [(15, 14), (16, 14), (14, 15), (15, 15), (15, 16)] {
+            board.revive_cell(&Coord::new(x, y));
+        }
+        let mut game = Game::new(board, rules::conways());
+        let initial = game.clone();
+
+        // The R-pentomino has 5, 6, 7, 9, 8 and then 9 live cells in its first generations
+        assert_eq!(game.first_population_over(5, 10), Some(1));
+        assert_eq!(game.first_population_over(8, 10), Some(3));
+        assert_eq!(game, initial);
+    }
+
+    #[test]
+    fn glider_leaves_board_with_dead_boundary() {
+        let mut board = Board::from_str(
+            &[
+                "________", "________", "_____#__", "______#_", "____###_", "________", "________",
+                "________",
+            ]
+            .join("\n"),
+            '#',
+        );
+        board.set_boundary(Boundary::Dead);
+        let mut game = Game::new(board, rules::conways());
+
+        for _ in 0..20 {
+            game.advance_to_next_gen();
+        }
+
+        // On a toroidal board the glider would have come back in the top left corner
+        let board = game.board();
+        assert!(board
+            .cell_coords()
+            .iter()
+            .filter(|c| c.x < 4 || c.y < 4)
+            .all(|c| board.get_cell_state(c) == &CellState::Dead));
+    }
+
+    #[test]
+    fn advance_generations_with_zero_leaves_board_untouched() {
+        let mut game = Game::new(vertical_blinker(), rules::conways());
+
+        game.advance_generations(0);
+
+        assert_eq!(game.board(), &vertical_blinker());
+    }
+
+    #[test]
+    fn advance_generations_matches_repeated_single_steps() {
+        let board = Board::with_glider_stream(16, 16, 2, 6);
+        let mut game = Game::new(board.clone(), rules::conways());
+        let mut stepped = Game::new(board, rules::conways());
+
+        game.advance_generations(7);
+        for _ in 0..7 {
+            stepped.advance_to_next_gen();
+        }
+
+        assert_eq!(game, stepped);
+    }
+
+    #[test]
+    fn advance_generations_stops_on_dead_board() {
+        let single_cell = Board::from_str(&["___", "_#_", "___"].join("\n"), '#');
+        let mut game = Game::new(single_cell, rules::conways());
+
+        game.advance_generations(usize::MAX);
+
+        assert!(game.board().is_empty());
+    }
+
+    #[test]
+    fn generation_counts_steps_but_not_manual_toggles() {
+        let mut game = Game::new(vertical_blinker(), rules::conways());
+
+        game.advance_to_next_gen();
+        game.advance_to_next_gen_sparse();
+        game.apply_toggles(&vec![&Coord::new(0, 0)]);
+        game.apply_toggles_deduped(&[Coord::new(0, 0)]);
+
+        assert_eq!(game.generation(), 2);
+
+        game.advance_generations(3);
+
+        assert_eq!(game.generation(), 5);
+    }
+
+    #[test]
+    fn generation_counts_all_requested_generations_of_dead_board() {
+        let mut game = Game::new(Board::new(4, 4), rules::conways());
+
+        game.advance_generations(10);
+
+        assert_eq!(game.generation(), 10);
+    }
+
+    #[test]
+    fn step_tells_whether_anything_changed() {
+        let mut blinker = Game::new(vertical_blinker(), rules::conways());
+        assert!(blinker.step());
+        assert!(blinker.step());
+
+        let mut block = Game::new(
+            Board::from_str(&["____", "_##_", "_##_", "____"].join("\n"), '#'),
+            rules::conways(),
+        );
+        assert!(!block.step());
+
+        let mut empty = Game::new(Board::new(3, 3), rules::conways());
+        assert!(!empty.step());
+    }
+
+    #[test]
+    fn stream_rle_writes_a_block_per_generation() {
+        let mut game = Game::new(vertical_blinker(), rules::conways());
+        let mut out = Vec::new();
+
+        game.stream_rle(&mut out, 2, &rules::conways()).unwrap();
+
+        let stream = String::from_utf8(out).unwrap();
+        let blocks: Vec<&str> = stream.trim_end().split("\n\n").collect();
+        assert_eq!(blocks.len(), 2);
+        assert!(blocks
+            .iter()
+            .all(|block| block.starts_with("x = ") && block.ends_with('!')));
+        assert_eq!(game.generation(), 2);
+    }
+
+    #[test]
+    fn activity_drift_of_glider_is_diagonal_across_the_seam() {
+        let mut board = Board::new(10, 10);
+        for (x, y) in [(7, 6), (8, 7), (6, 8), (7, 8), (8, 8)] {
+            board.revive_cell(&Coord::new(x, y));
+        }
+        let mut game = Game::new(board, rules::conways());
+        let initial = game.clone();
+
+        // Long enough for the glider to cross the edges of the board
+        let (dx, dy) = game.activity_drift(17);
+
+        assert_eq!((dx, dy), (0.25, 0.25));
+        assert_eq!(game, initial);
+    }
+
+    #[test]
+    fn activity_drift_of_blinker_is_zero() {
+        let mut game = Game::new(vertical_blinker(), rules::conways());
+
+        assert_eq!(game.activity_drift(10), (0.0, 0.0));
+    }
+
+    #[test]
+    fn von_neumann_rules_only_see_orthogonal_neighbours() {
+        let mut board = Board::new(7, 7);
+        board.revive_cell(&Coord::new(3, 3));
+        board.set_neighbourhood(Neighbourhood::VonNeumann);
+        let rules = Rules {
+            b: vec![1],
+            s: vec![0, 1, 2, 3, 4],
+        };
+        let mut game = Game::new(board, rules);
+
+        game.advance_to_next_gen();
+        game.advance_to_next_gen();
+
+        // Diagonal cells have two live neighbours in the second generation, so are not born
+        assert_eq!(
+            game.board().to_str('#', '_'),
+            ["_______", "___#___", "___#___", "_#####_", "___#___", "___#___", "_______"]
+                .join("\n")
+        );
+    }
+
+    #[test]
+    fn ash_period_histogram_counts_blocks_and_blinkers() {
+        // An L tromino becomes a block, and a T tetromino becomes a traffic light of four
+        // blinkers
+        let board = Board::from_str(
+            &[
+                "______________________",
+                "_#____________________",
+                "_##___________________",
+                "______________________",
+                "______________________",
+                "______________________",
+                "______________________",
+                "______________________",
+                "___________###________",
+                "____________#_________",
+                "______________________",
+                "______________________",
+                "______________________",
+                "______________________",
+                "______________________",
+                "______________________",
+                "______________________",
+                "______________________",
+            ]
+            .join("\n"),
+            '#',
+        );
+        let mut game = Game::new(board, rules::conways());
+
+        let histogram = game.ash_period_histogram(4);
+
+        assert_eq!(histogram, Some(HashMap::from([(1, 1), (2, 4)])));
+    }
+
+    #[test]
+    fn ash_period_histogram_gives_up_on_boards_which_never_settle() {
+        let board = Board::from_str(
+            &[
+                "__________",
+                "__#_______",
+                "___#______",
+                "_###______",
+                "__________",
+                "__________",
+                "__________",
+                "__________",
+            ]
+            .join("\n"),
+            '#',
+        );
+        let mut game = Game::new(board, rules::conways());
+
+        // The glider only comes back to where it started after 160 generations
+        assert_eq!(game.ash_period_histogram(3), None);
+        assert_eq!(game.generation(), 10_000);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialized_game_resumes_where_it_left_off() {
+        let mut game = Game::new(vertical_blinker(), rules::highlife());
+        game.set_max_age(Some(5));
+        game.advance_generations(3);
+
+        let json = serde_json::to_string(&game).unwrap();
+        let mut resumed: Game = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(resumed, game);
+
+        game.advance_to_next_gen();
+        resumed.advance_to_next_gen();
+
+        assert_eq!(resumed, game);
+    }
+
+    #[test]
+    fn immortal_cells_keep_seeding_a_pattern() {
+        // On its own, a row of three cells is a blinker. Kept from dying, it keeps giving birth to
+        // cells around it
+        let row = [Coord::new(3, 4), Coord::new(4, 4), Coord::new(5, 4)];
+        let mut game = Game::new(Board::new(9, 9), rules::conways());
+        game.set_immortal(&row);
+
+        for _ in 0..8 {
+            assert!(game.step());
+
+            for coord in &row {
+                assert_eq!(game.board().get_cell_state(coord), &CellState::Alive);
+            }
+        }
+
+        assert!(game.board().population() > 3);
+    }
+
+    #[test]
+    fn immortal_cells_give_same_result_with_sparse_stepping() {
+        let mut game = Game::new(vertical_blinker(), rules::conways());
+        game.set_immortal(&[Coord::new(2, 1), Coord::new(2, 3)]);
+        let mut sparse_game = game.clone();
+
+        for _ in 0..5 {
+            game.advance_to_next_gen();
+            sparse_game.advance_to_next_gen_sparse();
+
+            assert_eq!(game.board(), sparse_game.board());
+        }
+    }
+
+    #[test]
+    fn detect_cycle_restores_game() {
+        let mut game = Game::new(vertical_blinker(), rules::conways());
+        let original = game.clone();
+
+        assert_eq!(game.detect_cycle(4), Some(2));
+        assert_eq!(game, original);
+    }
+
+    #[test]
+    fn detect_cycle_of_evolving_pattern_is_none() {
+        let board = Board::from_str(
+            &[
+                "________________",
+                "________________",
+                "_______##_______",
+                "______##________",
+                "_______#________",
+                "________________",
+                "________________",
+                "________________",
+            ]
+            .join("\n"),
+            '#',
+        );
+        let mut game = Game::new(board, rules::conways());
+
+        assert_eq!(game.detect_cycle(20), None);
+    }
+
+    #[test]
+    fn stable_cells_of_block_next_to_blinker() {
+        let board = Board::from_str(
+            &[
+                "__________",
+                "_##_______",
+                "_##_______",
+                "__________",
+                "______#___",
+                "______#___",
+                "______#___",
+                "__________",
+            ]
+            .join("\n"),
+            '#',
+        );
+        let mut game = Game::new(board.clone(), rules::conways());
+
+        let stable_live_cells: Vec<Coord> = game
+            .stable_cells(4)
+            .into_iter()
+            .filter(|coord| board.get_cell_state(coord) == &CellState::Alive)
+            .collect();
+
+        // The middle of the blinker never changes either
+        assert_eq!(
+            stable_live_cells,
+            vec![
+                Coord::new(1, 1),
+                Coord::new(2, 1),
+                Coord::new(1, 2),
+                Coord::new(2, 2),
+                Coord::new(6, 5),
+            ]
+        );
+        assert_eq!(game.board(), &board);
+    }
+
+    #[test]
+    fn oscillator_bounding_box_of_blinker_covers_both_phases() {
+        let mut game = Game::new(vertical_blinker(), rules::conways());
+        let original = game.clone();
+
+        assert_eq!(game.oscillator_bounding_box(2), (Coord::new(1, 1), 3, 3));
+        // A single phase only covers one line
+        assert_eq!(game.oscillator_bounding_box(1), (Coord::new(2, 1), 1, 3));
+        assert_eq!(game, original);
+    }
+
+    #[test]
+    #[should_panic]
+    fn oscillator_bounding_box_of_empty_board_panics() {
+        Game::new(Board::new(4, 4), rules::conways()).oscillator_bounding_box(2);
+    }
+
+    #[test]
+    fn heat_of_blinker_is_4_and_of_block_is_0() {
+        let mut blinker = Game::new(vertical_blinker(), rules::conways());
+        let original = blinker.clone();
+        let mut block = Game::new(
+            Board::from_str(&["____", "_##_", "_##_", "____"].join("\n"), '#'),
+            rules::conways(),
+        );
+
+        assert_eq!(blinker.heat(2), 4.0);
+        assert_eq!(blinker, original);
+        assert_eq!(block.heat(1), 0.0);
+    }
+
+    #[test]
+    fn advance_region_only_evolves_blinker_inside_region() {
+        let board = Board::from_str(
+            &[
+                "__________",
+                "__#____#__",
+                "__#____#__",
+                "__#____#__",
+                "__________",
+            ]
+            .join("\n"),
+            '#',
+        );
+        let mut game = Game::new(board, rules::conways());
+
+        game.advance_region(Coord::new(5, 0), 100, 100);
+
+        assert_eq!(
+            game.board(),
+            &Board::from_str(
+                &[
+                    "__________",
+                    "__#_______",
+                    "__#___###_",
+                    "__#_______",
+                    "__________",
+                ]
+                .join("\n"),
+                '#',
+            )
+        );
+        assert_eq!(game.generation(), 0);
+    }
+
+    #[test]
+    fn advance_region_reads_neighbours_outside_region() {
+        // The middle cell of the blinker is outside the region, but its ends still see it
+        let mut game = Game::new(vertical_blinker(), rules::conways());
+
+        game.advance_region(Coord::new(1, 1), 3, 1);
+
+        assert_eq!(
+            game.board(),
+            &Board::from_str(
+                &["_____", "_____", "__#__", "__#__", "_____"].join("\n"),
+                '#'
+            )
+        );
+    }
+
+    #[test]
+    fn peak_population_of_dying_pattern_is_at_start() {
+        let mut game = Game::new(
+            Board::from_str(&["____", "_##_", "____"].join("\n"), '#'),
+            rules::conways(),
+        );
+        let original = game.clone();
+
+        assert_eq!(game.peak_population(3), (0, 2));
+        assert_eq!(game, original);
+    }
+
+    #[test]
+    fn peak_population_of_t_tetromino() {
+        // The T tetromino peaks at 20 cells in generation 8, right before it settles into a
+        // traffic light of four blinkers with 12 cells
+        let board = Board::from_str(
+            &[
+                "_____________",
+                "_____________",
+                "_____________",
+                "_____________",
+                "_____________",
+                "_____###_____",
+                "______#______",
+                "_____________",
+                "_____________",
+                "_____________",
+                "_____________",
+                "_____________",
+            ]
+            .join("\n"),
+            '#',
+        );
+        let mut game = Game::new(board, rules::conways());
+
+        assert_eq!(game.peak_population(20), (8, 20));
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn parallel_toggles_match_serial_for_any_thread_count() {
+        let game = Game::new(Board::random(67, 41, 0.35, 11), rules::conways());
+        let serial: Vec<&Coord> = game
+            .board()
+            .cell_coords()
+            .iter()
+            .filter(|c| {
+                let live_neighbours = game.board().get_live_neighbours_of(c);
+                match game.board().get_cell_state(c) {
+                    CellState::Alive => !game.rules().survives(live_neighbours),
+                    CellState::Dead => game.rules().birthed(live_neighbours),
+                }
+            })
+            .collect();
+
+        for threads in [1, 2, 3, 8] {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+
+            assert_eq!(pool.install(|| game.next_gen_toggles()), serial);
+        }
+    }
+
+    #[test]
+    fn blinker_phase_is_predecessor_of_next_phase() {
+        let game = Game::new(vertical_blinker(), rules::conways());
+        let horizontal = Board::from_str(
+            &["_____", "_____", "_###_", "_____", "_____"].join("\n"),
+            '#',
+        );
+
+        assert!(game.is_predecessor_of(&horizontal, &rules::conways()));
+        assert!(!game.is_predecessor_of(&vertical_blinker(), &rules::conways()));
+        assert!(!game.is_predecessor_of(&horizontal, &rules::seeds()));
+    }
+
+    #[test]
+    #[cfg(all(feature = "rayon", debug_assertions))]
+    fn parallel_steps_are_checked_against_serial_on_random_board() {
+        let mut game = Game::new(Board::random(53, 37, 0.4, 17), rules::highlife());
+
+        // Every step compares the parallel toggles with the serial ones, panicking on a mismatch
+        for _ in 0..20 {
+            game.advance_to_next_gen();
+        }
+
+        assert_eq!(game.generation(), 20);
+    }
+
+    #[test]
+    fn stream_rle_stays_parseable_after_pattern_dies() {
+        let mut board = Board::new(4, 4);
+        board.revive_cell(&Coord::new(1, 1));
+        let mut game = Game::new(board, rules::conways());
+
+        let mut out = Vec::new();
+        game.stream_rle(&mut out, 3, &rules::conways()).unwrap();
+
+        let out = String::from_utf8(out).unwrap();
+        let blocks: Vec<&str> = out.split("\n\n").filter(|b| !b.is_empty()).collect();
+        assert_eq!(blocks.len(), 3);
+        for block in blocks {
+            assert!(Board::from_rle(block).is_ok());
+        }
+    }
+
+    #[test]
+    fn cells_die_of_old_age_despite_surviving_neighbours() {
+        let block = Board::from_str(&["______", "_##___", "_##___", "______"].join("\n"), '#');
+        let mut game = Game::new(block.clone(), rules::conways());
+        game.set_max_age(Some(3));
+        let mut sparse_game = game.clone();
+        let mut region_game = game.clone();
+
+        for _ in 0..2 {
+            game.advance_to_next_gen();
+            sparse_game.advance_to_next_gen_sparse();
+            region_game.advance_region(Coord::new(0, 0), 6, 4);
+
+            assert_eq!(game.board(), &block);
+            assert_eq!(sparse_game.board(), &block);
+            assert_eq!(region_game.board(), &block);
+        }
+
+        game.advance_to_next_gen();
+        sparse_game.advance_to_next_gen_sparse();
+        region_game.advance_region(Coord::new(0, 0), 6, 4);
+
+        assert!(game.board().is_empty());
+        assert!(sparse_game.board().is_empty());
+        assert!(region_game.board().is_empty());
+    }
+
+    #[test]
+    fn cells_born_after_others_age_out_age_from_birth() {
+        // The blinker's end cells die and are born again every generation, so only the center
+        // cell ever reaches the maximum age
+        let mut game = Game::new(vertical_blinker(), rules::conways());
+        game.set_max_age(Some(2));
+
+        game.advance_to_next_gen();
+        assert_eq!(game.board().population(), 3);
+
+        game.advance_to_next_gen();
+        assert_eq!(game.board().population(), 2);
+        assert_eq!(
+            game.board().get_cell_state(&Coord::new(2, 2)),
+            &CellState::Dead
+        );
     }
 }
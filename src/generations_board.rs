@@ -0,0 +1,351 @@
+use crate::board::Board;
+use crate::boundary::Boundary;
+use crate::coord::{Coord, NEIGHBOUR_OFFSETS};
+use crate::generations_cell::GenerationsCell;
+use crate::neighbourhood::Neighbourhood;
+use crate::rules::GenerationsRules;
+
+/// A toroidal board for Generations rules, where cells which die go through a number of dying
+/// states before they are dead. See [`GenerationsRules`]
+///
+/// # Examples
+/// ```
+/// use game_of_life::{GenerationsBoard, GenerationsCell, Coord, rules};
+///
+/// let mut board = GenerationsBoard::new(6, 6);
+/// board.set_cell(&Coord::new(2, 2), GenerationsCell::Alive);
+/// board.set_cell(&Coord::new(3, 2), GenerationsCell::Alive);
+///
+/// board.advance(&rules::brians_brain());
+///
+/// assert_eq!(board.to_str('#', '+', '_'), vec![
+///     "______",
+///     "__##__",
+///     "__++__",
+///     "__##__",
+///     "______",
+///     "______",
+/// ].join("\n"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenerationsBoard {
+    width: usize,
+    height: usize,
+    /// States of the cells, row by row
+    cells: Vec<GenerationsCell>,
+}
+
+impl GenerationsBoard {
+    /// Creates a new board with the desired size, initializing all cells as dead
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::GenerationsBoard;
+    ///
+    /// let board = GenerationsBoard::new(3, 2);
+    ///
+    /// assert_eq!(board.width(), 3);
+    /// assert_eq!(board.height(), 2);
+    /// assert_eq!(board.population(), 0);
+    /// ```
+    ///
+    /// # Panics
+    /// If width or height is 0
+    pub fn new(width: usize, height: usize) -> GenerationsBoard {
+        if width == 0 || height == 0 {
+            panic!("Width and height must be at least 1");
+        }
+
+        GenerationsBoard {
+            width,
+            height,
+            cells: vec![GenerationsCell::Dead; width * height],
+        }
+    }
+
+    /// Creates a board with the same size as a board, with its live cells alive and every other
+    /// cell dead. Generations boards always wrap around and use the Moore neighbourhood, so only
+    /// boards with the same settings can be converted
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, GenerationsBoard};
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "#__",
+    ///     "_#_",
+    /// ].join("\n"), '#');
+    ///
+    /// let generations = GenerationsBoard::from_board(&board);
+    ///
+    /// assert_eq!(generations.to_str('#', '+', '_'), vec![
+    ///     "#__",
+    ///     "_#_",
+    /// ].join("\n"));
+    /// ```
+    ///
+    /// # Panics
+    /// If the board does not have a toroidal boundary and the Moore neighbourhood
+    pub fn from_board(board: &Board) -> GenerationsBoard {
+        if board.boundary() != Boundary::Toroidal || board.neighbourhood() != Neighbourhood::Moore {
+            panic!("Only toroidal boards with the Moore neighbourhood can be converted");
+        }
+
+        let mut generations = GenerationsBoard::new(board.width(), board.height());
+
+        for coord in board.live_cells() {
+            generations.set_cell(coord, GenerationsCell::Alive);
+        }
+
+        generations
+    }
+
+    /// Creates a board with the same size, where only the live cells are alive. Dying cells are
+    /// dead on the new board
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Coord, GenerationsBoard, GenerationsCell};
+    ///
+    /// let mut generations = GenerationsBoard::new(3, 1);
+    /// generations.set_cell(&Coord::new(0, 0), GenerationsCell::Alive);
+    /// generations.set_cell(&Coord::new(1, 0), GenerationsCell::Dying(1));
+    ///
+    /// assert_eq!(generations.to_board().to_str('#', '_'), "#__");
+    /// ```
+    pub fn to_board(&self) -> Board {
+        let mut board = Board::new(self.width, self.height);
+
+        for coord in self.coords() {
+            if self.get_cell(&coord) == GenerationsCell::Alive {
+                board.revive_cell(&coord);
+            }
+        }
+
+        board
+    }
+
+    /// Gets the width of the board
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Gets the height of the board
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Counts the live cells on the board. Dying cells are not counted
+    pub fn population(&self) -> usize {
+        self.cells
+            .iter()
+            .filter(|&&cell| cell == GenerationsCell::Alive)
+            .count()
+    }
+
+    /// Gets the state of the cell at the given coordinate
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Coord, GenerationsBoard, GenerationsCell};
+    ///
+    /// let board = GenerationsBoard::new(2, 2);
+    ///
+    /// assert_eq!(board.get_cell(&Coord::new(1, 1)), GenerationsCell::Dead);
+    /// ```
+    ///
+    /// # Panics
+    /// If the coordinate is outside the board
+    pub fn get_cell(&self, coord: &Coord) -> GenerationsCell {
+        self.cells[self.index(coord)]
+    }
+
+    /// Sets the state of the cell at the given coordinate
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Coord, GenerationsBoard, GenerationsCell};
+    ///
+    /// let mut board = GenerationsBoard::new(2, 2);
+    /// board.set_cell(&Coord::new(1, 0), GenerationsCell::Dying(1));
+    ///
+    /// assert_eq!(board.get_cell(&Coord::new(1, 0)), GenerationsCell::Dying(1));
+    /// ```
+    ///
+    /// # Panics
+    /// If the coordinate is outside the board
+    pub fn set_cell(&mut self, coord: &Coord, cell: GenerationsCell) {
+        let index = self.index(coord);
+        self.cells[index] = cell;
+    }
+
+    /// Finds the index of a coordinate in the cell list
+    ///
+    /// # Panics
+    /// If the coordinate is outside the board
+    fn index(&self, coord: &Coord) -> usize {
+        if coord.x >= self.width || coord.y >= self.height {
+            panic!("Coordinate {:?} is outside the board", coord);
+        }
+
+        coord.y * self.width + coord.x
+    }
+
+    /// Iterates over all coordinates on the board, row by row
+    fn coords(&self) -> impl Iterator<Item = Coord> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Coord::new(x, y)))
+    }
+
+    /// Advances the board to the next generation under the given rules, with the edges wrapping
+    /// around. Dead cells with a birthing number of live neighbours are born, live cells with a
+    /// surviving number of live neighbours stay alive, and every other live or dying cell moves
+    /// one state closer to death
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Coord, GenerationsBoard, GenerationsCell, rules};
+    ///
+    /// let mut board = GenerationsBoard::new(5, 5);
+    /// board.set_cell(&Coord::new(2, 2), GenerationsCell::Alive);
+    ///
+    /// // Star Wars has 4 states, so a lone cell lingers for two generations before it is dead
+    /// board.advance(&rules::star_wars());
+    /// assert_eq!(board.get_cell(&Coord::new(2, 2)), GenerationsCell::Dying(1));
+    ///
+    /// board.advance(&rules::star_wars());
+    /// assert_eq!(board.get_cell(&Coord::new(2, 2)), GenerationsCell::Dying(2));
+    ///
+    /// board.advance(&rules::star_wars());
+    /// assert_eq!(board.get_cell(&Coord::new(2, 2)), GenerationsCell::Dead);
+    /// ```
+    pub fn advance(&mut self, rules: &GenerationsRules) {
+        let mut counts = vec![0u8; self.cells.len()];
+        for coord in self.coords() {
+            if self.get_cell(&coord) != GenerationsCell::Alive {
+                continue;
+            }
+
+            for (dx, dy) in NEIGHBOUR_OFFSETS {
+                let neighbour = coord.translate_wrapping(dx, dy, self.width, self.height);
+                counts[self.index(&neighbour)] += 1;
+            }
+        }
+
+        let last_dying = rules.states.saturating_sub(2);
+        let decay = |age: u8| {
+            if age < last_dying {
+                GenerationsCell::Dying(age + 1)
+            } else {
+                GenerationsCell::Dead
+            }
+        };
+
+        for (cell, live_neighbours) in self.cells.iter_mut().zip(counts) {
+            *cell = match *cell {
+                GenerationsCell::Dead if rules.birthed(live_neighbours) => GenerationsCell::Alive,
+                GenerationsCell::Dead => GenerationsCell::Dead,
+                GenerationsCell::Alive if rules.survives(live_neighbours) => GenerationsCell::Alive,
+                GenerationsCell::Alive => decay(0),
+                GenerationsCell::Dying(age) => decay(age),
+            };
+        }
+    }
+
+    /// Creates a string representation of the board, with one line per row and the given
+    /// characters for live, dying and dead cells
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Coord, GenerationsBoard, GenerationsCell};
+    ///
+    /// let mut board = GenerationsBoard::new(3, 2);
+    /// board.set_cell(&Coord::new(0, 0), GenerationsCell::Alive);
+    /// board.set_cell(&Coord::new(2, 1), GenerationsCell::Dying(1));
+    ///
+    /// assert_eq!(board.to_str('#', '+', '_'), vec![
+    ///     "#__",
+    ///     "__+",
+    /// ].join("\n"));
+    /// ```
+    pub fn to_str(&self, alive: char, dying: char, dead: char) -> String {
+        self.cells
+            .chunks(self.width)
+            .map(|row| {
+                row.iter()
+                    .map(|cell| match cell {
+                        GenerationsCell::Alive => alive,
+                        GenerationsCell::Dying(_) => dying,
+                        GenerationsCell::Dead => dead,
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::Game;
+    use crate::rules;
+
+    #[test]
+    fn two_state_rules_match_game() {
+        for rules in [rules::conways(), rules::highlife(), rules::day_and_night()] {
+            let board = Board::random(11, 8, 0.4, 5);
+            let mut generations = GenerationsBoard::from_board(&board);
+            let generations_rules = GenerationsRules::from(rules.clone());
+            let mut game = Game::new(board, rules);
+
+            for _ in 0..10 {
+                generations.advance(&generations_rules);
+                game.advance_to_next_gen();
+
+                assert_eq!(&generations.to_board(), game.board());
+            }
+        }
+    }
+
+    #[test]
+    fn dying_cells_age_until_dead() {
+        let mut board = GenerationsBoard::new(5, 5);
+        let coord = Coord::new(2, 2);
+        board.set_cell(&coord, GenerationsCell::Alive);
+        let star_wars = rules::star_wars();
+
+        board.advance(&star_wars);
+        assert_eq!(board.get_cell(&coord), GenerationsCell::Dying(1));
+
+        board.advance(&star_wars);
+        assert_eq!(board.get_cell(&coord), GenerationsCell::Dying(2));
+
+        board.advance(&star_wars);
+        assert_eq!(board.get_cell(&coord), GenerationsCell::Dead);
+    }
+
+    #[test]
+    fn dying_cells_are_neither_born_nor_counted_as_neighbours() {
+        let mut board = GenerationsBoard::new(5, 5);
+        board.set_cell(&Coord::new(1, 2), GenerationsCell::Dying(1));
+        board.set_cell(&Coord::new(2, 2), GenerationsCell::Alive);
+        board.set_cell(&Coord::new(3, 2), GenerationsCell::Dying(1));
+
+        board.advance(&rules::brians_brain());
+
+        // Only the single live cell counts, so nothing has two live neighbours
+        assert_eq!(board.population(), 0);
+        assert_eq!(board.get_cell(&Coord::new(2, 2)), GenerationsCell::Dying(1));
+        assert_eq!(board.get_cell(&Coord::new(1, 2)), GenerationsCell::Dead);
+    }
+
+    #[test]
+    #[should_panic]
+    fn converting_board_with_other_settings_panics() {
+        let mut board = Board::new(4, 4);
+        board.set_boundary(Boundary::KleinBottle);
+
+        GenerationsBoard::from_board(&board);
+    }
+}
@@ -0,0 +1,11 @@
+/// The state of a cell under Generations rules
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GenerationsCell {
+    /// The cell is alive and counts as a live neighbour
+    Alive,
+    /// The cell is dying, having left the live state the given number of generations ago
+    Dying(u8),
+    /// The cell is dead and may be born
+    Dead,
+}
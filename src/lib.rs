@@ -1,10 +1,44 @@
 mod board;
+mod boundary;
 mod cell_state;
+mod cell_transition;
+mod colors;
 mod coord;
+mod font;
 mod game;
+mod generations_board;
+mod generations_cell;
+mod neighbourhood;
+mod parse_error;
+pub mod patterns;
+mod rle;
+mod rng;
 pub mod rules;
+mod share_code;
+mod sparse_board;
+mod stamp_mode;
+mod symmetry_kind;
+#[cfg(feature = "serde")]
+mod template;
+mod velocity;
 
 pub use board::Board;
+pub use boundary::Boundary;
 pub use cell_state::CellState;
+pub use cell_transition::CellTransition;
+pub use colors::Colors;
 pub use coord::Coord;
+pub use font::Font;
 pub use game::Game;
+pub use generations_board::GenerationsBoard;
+pub use generations_cell::GenerationsCell;
+pub use neighbourhood::Neighbourhood;
+pub use parse_error::ParseError;
+pub use rle::RleError;
+pub use share_code::DecodeError;
+pub use sparse_board::SparseBoard;
+pub use stamp_mode::StampMode;
+pub use symmetry_kind::SymmetryKind;
+#[cfg(feature = "serde")]
+pub use template::{BoardTemplate, Placement};
+pub use velocity::Velocity;
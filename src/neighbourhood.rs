@@ -0,0 +1,10 @@
+/// Which cells around a cell count as its neighbours
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Neighbourhood {
+    /// The eight cells surrounding the cell, including the diagonal ones
+    #[default]
+    Moore,
+    /// The four cells to the north, east, south and west of the cell
+    VonNeumann,
+}
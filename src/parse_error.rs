@@ -0,0 +1,77 @@
+use std::error::Error;
+use std::fmt;
+
+/// Errors which can occur when parsing a board from text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The text contained no cells
+    Empty,
+    /// A row has a different number of cells than the rows before it
+    RaggedRow {
+        /// Index of the offending row
+        row: usize,
+        /// Number of cells in the rows before it
+        expected: usize,
+        /// Number of cells in the offending row
+        found: usize,
+    },
+    /// A token which does not represent a cell state
+    InvalidToken {
+        /// Index of the row the token is on
+        row: usize,
+        /// Index of the column the token is in
+        column: usize,
+        /// The offending token
+        token: String,
+    },
+    /// The pattern has more than 2^24 cells, like a 4096 by 4096 board
+    TooLarge,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "No cells to parse"),
+            ParseError::RaggedRow {
+                row,
+                expected,
+                found,
+            } => write!(f, "Row {} has {} cells, expected {}", row, found, expected),
+            ParseError::InvalidToken { row, column, token } => write!(
+                f,
+                "Invalid token '{}' at row {}, column {}",
+                token, row, column
+            ),
+            ParseError::TooLarge => write!(f, "Pattern has too many cells"),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ragged_row_displays_correctly() {
+        let err = ParseError::RaggedRow {
+            row: 2,
+            expected: 3,
+            found: 4,
+        };
+
+        assert_eq!(format!("{}", err), "Row 2 has 4 cells, expected 3");
+    }
+
+    #[test]
+    fn invalid_token_displays_correctly() {
+        let err = ParseError::InvalidToken {
+            row: 1,
+            column: 0,
+            token: "x".to_string(),
+        };
+
+        assert_eq!(format!("{}", err), "Invalid token 'x' at row 1, column 0");
+    }
+}
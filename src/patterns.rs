@@ -0,0 +1,278 @@
+use crate::board::Board;
+use crate::parse_error::ParseError;
+use crate::rle::RleError;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// A glider heading south east
+///
+/// # Examples
+/// ```
+/// use game_of_life::patterns;
+///
+/// let glider = patterns::glider();
+///
+/// assert_eq!(glider.to_str('#', '_'), vec![
+///     "_#_",
+///     "__#",
+///     "###",
+/// ].join("\n"));
+/// ```
+pub fn glider() -> Board {
+    Board::from_str(&["_#_", "__#", "###"].join("\n"), '#')
+}
+
+/// A block, the most common still life
+///
+/// # Examples
+/// ```
+/// use game_of_life::patterns;
+///
+/// let block = patterns::block();
+///
+/// assert_eq!(block.to_str('#', '_'), vec![
+///     "##",
+///     "##",
+/// ].join("\n"));
+/// ```
+pub fn block() -> Board {
+    Board::from_str(&["##", "##"].join("\n"), '#')
+}
+
+/// A blinker, the most common oscillator, in its vertical phase
+///
+/// # Examples
+/// ```
+/// use game_of_life::patterns;
+///
+/// let blinker = patterns::blinker();
+///
+/// assert_eq!(blinker.to_str('#', '_'), vec![
+///     "#",
+///     "#",
+///     "#",
+/// ].join("\n"));
+/// ```
+pub fn blinker() -> Board {
+    Board::from_str(&["#", "#", "#"].join("\n"), '#')
+}
+
+/// Looks up a pattern by its name. Known names are `glider`, `block` and `blinker`
+///
+/// # Examples
+/// ```
+/// use game_of_life::patterns;
+///
+/// assert_eq!(patterns::by_name("glider"), Some(patterns::glider()));
+/// assert_eq!(patterns::by_name("unicorn"), None);
+/// ```
+pub fn by_name(name: &str) -> Option<Board> {
+    match name {
+        "glider" => Some(glider()),
+        "block" => Some(block()),
+        "blinker" => Some(blinker()),
+        _ => None,
+    }
+}
+
+/// Errors which can occur when loading patterns from a directory. Every error except failing to
+/// read the directory itself names the file it happened in
+#[derive(Debug)]
+pub enum LoadError {
+    /// The directory or a file in it could not be read
+    Io {
+        /// Name of the file, or the path of the directory
+        file: String,
+        /// The underlying error
+        error: io::Error,
+    },
+    /// An `.rle` file is not a valid RLE pattern
+    Rle {
+        /// Name of the file
+        file: String,
+        /// What is wrong with the pattern
+        error: RleError,
+    },
+    /// A `.cells` file is not a valid plaintext pattern
+    Cells {
+        /// Name of the file
+        file: String,
+        /// What is wrong with the pattern
+        error: ParseError,
+    },
+    /// A `.life` file is not a valid Life 1.06 pattern
+    Life {
+        /// Name of the file
+        file: String,
+        /// What is wrong with the pattern
+        error: ParseError,
+    },
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoadError::Io { file, error } => write!(f, "Could not read {}: {}", file, error),
+            LoadError::Rle { file, error } => write!(f, "Invalid RLE in {}: {}", file, error),
+            LoadError::Cells { file, error } => {
+                write!(f, "Invalid plaintext pattern in {}: {}", file, error)
+            }
+            LoadError::Life { file, error } => {
+                write!(f, "Invalid Life 1.06 pattern in {}: {}", file, error)
+            }
+        }
+    }
+}
+
+impl Error for LoadError {}
+
+/// Loads every pattern file in a directory, parsing each by its extension: `.rle` files as Run
+/// Length Encoded patterns, `.cells` files as plaintext and `.life` files as Life 1.06. Files with
+/// other extensions and subdirectories are skipped. The patterns are returned with their file
+/// names, sorted by file name
+///
+/// # Errors
+/// If the directory or a pattern file in it cannot be read, or if a pattern is malformed
+pub fn load_dir(path: &Path) -> Result<Vec<(String, Board)>, LoadError> {
+    let io_error = |file: &Path| {
+        let file = file.display().to_string();
+        move |error| LoadError::Io { file, error }
+    };
+
+    let mut patterns = Vec::new();
+
+    for entry in fs::read_dir(path).map_err(io_error(path))? {
+        let entry = entry.map_err(io_error(path))?;
+        let entry_path = entry.path();
+        if entry_path.is_dir() {
+            continue;
+        }
+
+        let extension = entry_path.extension().and_then(|ext| ext.to_str());
+        if !matches!(extension, Some("rle" | "cells" | "life")) {
+            continue;
+        }
+
+        let file = entry.file_name().to_string_lossy().into_owned();
+        let text = fs::read_to_string(&entry_path).map_err(io_error(&entry_path))?;
+
+        let board = match extension {
+            Some("rle") => Board::from_rle(&text).map_err(|error| LoadError::Rle {
+                file: file.clone(),
+                error,
+            })?,
+            Some("cells") => parse_cells(&text).map_err(|error| LoadError::Cells {
+                file: file.clone(),
+                error,
+            })?,
+            _ => Board::from_life_106(&text).map_err(|error| LoadError::Life {
+                file: file.clone(),
+                error,
+            })?,
+        };
+
+        patterns.push((file, board));
+    }
+
+    patterns.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    Ok(patterns)
+}
+
+/// Parses a plaintext `.cells` pattern like [`Board::from_cells`], but reports characters other
+/// than `O` and `.` and patterns with no cells as errors rather than accepting or panicking
+fn parse_cells(text: &str) -> Result<Board, ParseError> {
+    let rows: Vec<&str> = text
+        .trim_end_matches(['\n', '\r'])
+        .lines()
+        .filter(|line| !line.starts_with('!'))
+        .collect();
+
+    for (row, line) in rows.iter().enumerate() {
+        if let Some((column, c)) = line
+            .chars()
+            .enumerate()
+            .find(|(_, c)| !matches!(c, 'O' | '.'))
+        {
+            return Err(ParseError::InvalidToken {
+                row,
+                column,
+                token: c.to_string(),
+            });
+        }
+    }
+
+    if rows.iter().all(|line| line.is_empty()) {
+        return Err(ParseError::Empty);
+    }
+
+    Ok(Board::from_cells(text))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// Makes an empty scratch directory for a test
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("game_of_life_{}", name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn load_dir_reads_fixture_patterns() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/patterns");
+
+        let patterns = load_dir(&dir).unwrap();
+
+        assert_eq!(
+            patterns,
+            vec![
+                ("blinker.life".to_string(), blinker()),
+                ("block.cells".to_string(), block()),
+                ("glider.rle".to_string(), glider()),
+            ]
+        );
+    }
+
+    #[test]
+    fn load_dir_names_file_with_malformed_pattern() {
+        let dir = scratch_dir("load_dir_malformed");
+        fs::write(dir.join("good.cells"), "OO\nOO\n").unwrap();
+        fs::write(dir.join("bad.cells"), "OO\nOx\n").unwrap();
+
+        match load_dir(&dir) {
+            Err(LoadError::Cells { file, error }) => {
+                assert_eq!(file, "bad.cells");
+                assert_eq!(
+                    error,
+                    ParseError::InvalidToken {
+                        row: 1,
+                        column: 1,
+                        token: "x".to_string()
+                    }
+                );
+            }
+            result => panic!("Expected a .cells error, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn load_dir_skips_unrelated_files() {
+        let dir = scratch_dir("load_dir_unrelated");
+        fs::write(dir.join("block.cells"), "OO\nOO\n").unwrap();
+        fs::write(dir.join("README"), "Some patterns").unwrap();
+        fs::write(dir.join(".DS_Store"), [0, 159, 146, 150]).unwrap();
+        fs::write(dir.join("notes.txt"), "#Life 1.06\n0 0\n").unwrap();
+
+        let patterns = load_dir(&dir).unwrap();
+
+        assert_eq!(patterns, vec![("block.cells".to_string(), block())]);
+    }
+}
@@ -0,0 +1,454 @@
+use crate::board::Board;
+use crate::cell_state::CellState;
+use crate::coord::Coord;
+use crate::rules::{ParseRulesError, Rules};
+use std::error::Error;
+use std::fmt;
+
+/// Maximum length of a line in an RLE body, as recommended by the format
+const MAX_LINE_LENGTH: usize = 70;
+
+/// Largest number of cells in a decoded board. The header of an RLE file can claim any size, so
+/// this keeps a short file from allocating a huge board
+pub(crate) const MAX_DECODED_CELLS: usize = 1 << 24;
+
+/// Errors which can occur when reading an RLE file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RleError {
+    /// There is no `x = .., y = ..` header line before the pattern
+    MissingHeader,
+    /// The header line could not be understood
+    InvalidHeader(String),
+    /// The header describes a pattern with no rows or no columns
+    InvalidDimensions,
+    /// The header describes a pattern with more than 2^24 cells, like a 4096 by 4096 board
+    TooLarge,
+    /// The rule in the header is not valid B/S notation
+    InvalidRule(ParseRulesError),
+    /// A character in the body which is not a run count or a tag
+    InvalidToken(char),
+    /// A live cell outside the dimensions given in the header
+    OutOfBounds(Coord),
+    /// A run count, or a position reached by a run, too large to represent
+    Overflow,
+}
+
+impl fmt::Display for RleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RleError::MissingHeader => write!(f, "Missing header line"),
+            RleError::InvalidHeader(line) => write!(f, "Invalid header line '{}'", line),
+            RleError::InvalidDimensions => write!(f, "Pattern has invalid dimensions"),
+            RleError::TooLarge => write!(f, "Pattern has more than {} cells", MAX_DECODED_CELLS),
+            RleError::InvalidRule(err) => write!(f, "Invalid rule: {}", err),
+            RleError::InvalidToken(c) => write!(f, "Invalid token '{}' in pattern", c),
+            RleError::OutOfBounds(coord) => write!(
+                f,
+                "Live cell at ({}, {}) is outside the pattern",
+                coord.x, coord.y
+            ),
+            RleError::Overflow => write!(f, "Run count too large"),
+        }
+    }
+}
+
+impl Error for RleError {}
+
+/// Makes the `x = .., y = .., rule = ..` header line of an RLE file
+pub fn header(width: usize, height: usize, rules: &Rules) -> String {
+    format!("{}, rule = {}", dimensions_header(width, height), rules)
+}
+
+/// Makes the `x = .., y = ..` header line of an RLE file without a rule, which readers take to
+/// mean Conway's Game of Life
+pub fn dimensions_header(width: usize, height: usize) -> String {
+    format!("x = {}, y = {}", width, height)
+}
+
+/// Encodes rows of cells as an RLE body, terminated by `!`. Dead cells at the end of a row and
+/// empty rows at the end of the pattern are omitted
+pub fn encode<'a, I: IntoIterator<Item = &'a [CellState]>>(rows: I) -> String {
+    let mut tokens = Vec::new();
+    let mut pending_rows = 0;
+
+    for row in rows {
+        let runs = runs(row);
+
+        // Rows with no live cells are merged into the row separator of the next non-empty row
+        if runs.is_empty() {
+            pending_rows += 1;
+            continue;
+        }
+
+        // The first row with live cells only needs to skip the empty rows above it, while later
+        // ones also need to end the row before them
+        if !tokens.is_empty() {
+            tokens.push(run_token(pending_rows + 1, '$'));
+        } else if pending_rows > 0 {
+            tokens.push(run_token(pending_rows, '$'));
+        }
+        pending_rows = 0;
+
+        for (cell_state, count) in runs {
+            let tag = match cell_state {
+                CellState::Alive => 'o',
+                CellState::Dead => 'b',
+            };
+            tokens.push(run_token(count, tag));
+        }
+    }
+
+    tokens.push("!".to_string());
+
+    wrap(&tokens)
+}
+
+/// Reads the `x = .., y = .., rule = ..` header line of an RLE file, giving the width, height and
+/// rules if there are any. The rule may be in B/S notation like `B3/S23`, or in the older S/B
+/// notation with only digits like `23/3`. A bounded grid suffix like Golly's `:T20,20` is ignored,
+/// as the size of the board is given by the header
+pub fn parse_header(line: &str) -> Result<(usize, usize, Option<Rules>), RleError> {
+    let invalid = || RleError::InvalidHeader(line.to_string());
+
+    let mut width = None;
+    let mut height = None;
+    let mut rules = None;
+
+    // The rule is the last field, and a bounded grid suffix may contain commas of its own
+    for field in line.splitn(3, ',') {
+        let (key, value) = field.split_once('=').ok_or_else(invalid)?;
+        let value = value.trim();
+
+        match key.trim() {
+            "x" => width = Some(value.parse::<usize>().map_err(|_| invalid())?),
+            "y" => height = Some(value.parse::<usize>().map_err(|_| invalid())?),
+            "rule" => rules = Some(parse_rule(value).map_err(RleError::InvalidRule)?),
+            _ => return Err(invalid()),
+        }
+    }
+
+    match (width, height) {
+        (Some(width), Some(height)) => Ok((width, height, rules)),
+        _ => Err(invalid()),
+    }
+}
+
+/// Parses the rule of an RLE header, dropping any bounded grid suffix and turning the digits only
+/// S/B notation into B/S notation
+fn parse_rule(value: &str) -> Result<Rules, ParseRulesError> {
+    let rule = value.split_once(':').map_or(value, |(rule, _)| rule).trim();
+
+    match rule.split_once('/') {
+        Some((s, b)) if rule.chars().all(|c| c.is_ascii_digit() || c == '/') => {
+            format!("B{}/S{}", b, s).parse()
+        }
+        _ => rule.parse(),
+    }
+}
+
+/// Reads the width and height from the header of an RLE file, skipping lines starting with `#`
+/// before it. The lines after the header are returned without being looked at
+fn split_header(text: &str) -> Result<(usize, usize, impl Iterator<Item = &str>), RleError> {
+    let mut lines = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'));
+
+    let (width, height, _) = parse_header(lines.next().ok_or(RleError::MissingHeader)?)?;
+    if width == 0 || height == 0 {
+        return Err(RleError::InvalidDimensions);
+    }
+
+    Ok((width, height, lines))
+}
+
+/// Reads the width and height of the board in an RLE file from its header, without decoding the
+/// pattern
+pub fn dimensions(text: &str) -> Result<(usize, usize), RleError> {
+    let (width, height, _) = split_header(text)?;
+
+    Ok((width, height))
+}
+
+/// Decodes an RLE file into a board the size given in its header. Lines starting with `#` before
+/// the header are skipped, and everything after the `!` ending the pattern is ignored. Headers
+/// describing more than `MAX_DECODED_CELLS` cells are rejected before the board is allocated
+pub fn decode(text: &str) -> Result<Board, RleError> {
+    let (width, height, lines) = split_header(text)?;
+    match width.checked_mul(height) {
+        Some(cells) if cells <= MAX_DECODED_CELLS => {}
+        _ => return Err(RleError::TooLarge),
+    }
+
+    let mut board = Board::new(width, height);
+    let mut x: usize = 0;
+    let mut y: usize = 0;
+    let mut count: Option<usize> = None;
+
+    for c in lines.flat_map(str::chars) {
+        if let Some(digit) = c.to_digit(10) {
+            count = count
+                .unwrap_or(0)
+                .checked_mul(10)
+                .and_then(|count| count.checked_add(digit as usize));
+            if count.is_none() {
+                return Err(RleError::Overflow);
+            }
+            continue;
+        }
+
+        let run = count.take().unwrap_or(1);
+        match c {
+            'b' => x = x.checked_add(run).ok_or(RleError::Overflow)?,
+            'o' => {
+                for _ in 0..run {
+                    if x >= width || y >= height {
+                        return Err(RleError::OutOfBounds(Coord::new(x, y)));
+                    }
+                    board.revive_cell(&Coord::new(x, y));
+                    x += 1;
+                }
+            }
+            '$' => {
+                x = 0;
+                y = y.checked_add(run).ok_or(RleError::Overflow)?;
+            }
+            '!' => break,
+            c if c.is_whitespace() => {}
+            c => return Err(RleError::InvalidToken(c)),
+        }
+    }
+
+    Ok(board)
+}
+
+/// Collapses a row into runs of identical cells, leaving out trailing dead cells
+fn runs(row: &[CellState]) -> Vec<(&CellState, usize)> {
+    let mut runs: Vec<(&CellState, usize)> = Vec::new();
+
+    for cell_state in row {
+        match runs.last_mut() {
+            Some((state, count)) if *state == cell_state => *count += 1,
+            _ => runs.push((cell_state, 1)),
+        }
+    }
+
+    if let Some((CellState::Dead, _)) = runs.last() {
+        runs.pop();
+    }
+
+    runs
+}
+
+/// Makes a single RLE token, leaving out the count if it is 1
+fn run_token(count: usize, tag: char) -> String {
+    if count == 1 {
+        tag.to_string()
+    } else {
+        format!("{}{}", count, tag)
+    }
+}
+
+/// Joins tokens into lines no longer than the maximum line length, never splitting a token
+fn wrap(tokens: &[String]) -> String {
+    let mut lines = vec![String::new()];
+
+    for token in tokens {
+        let line = lines.last_mut().unwrap();
+
+        if !line.is_empty() && line.len() + token.len() > MAX_LINE_LENGTH {
+            lines.push(token.clone());
+        } else {
+            line.push_str(token);
+        }
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules;
+
+    #[test]
+    fn header_contains_dimensions_and_rule() {
+        assert_eq!(
+            header(3, 2, &rules::conways()),
+            "x = 3, y = 2, rule = B3/S23"
+        );
+    }
+
+    #[test]
+    fn encode_collapses_runs_and_omits_trailing_dead_cells() {
+        use CellState::{Alive as O, Dead as B};
+
+        let rows: Vec<Vec<CellState>> = vec![vec![B, O, B], vec![B, B, O], vec![O, O, O]];
+
+        assert_eq!(encode(rows.iter().map(Vec::as_slice)), "bo$2bo$3o!");
+    }
+
+    #[test]
+    fn encode_merges_empty_rows() {
+        use CellState::{Alive as O, Dead as B};
+
+        let rows: Vec<Vec<CellState>> = vec![vec![O, B], vec![B, B], vec![B, B], vec![B, O]];
+
+        assert_eq!(encode(rows.iter().map(Vec::as_slice)), "o3$bo!");
+    }
+
+    #[test]
+    fn encode_wraps_long_lines() {
+        use CellState::{Alive as O, Dead as B};
+
+        let row: Vec<CellState> = (0..100).map(|x| if x % 2 == 0 { O } else { B }).collect();
+
+        let body = encode(std::iter::once(row.as_slice()));
+
+        assert!(body.lines().count() > 1);
+        assert!(body.lines().all(|line| line.len() <= MAX_LINE_LENGTH));
+    }
+
+    #[test]
+    fn parse_header_reads_dimensions_and_rule() {
+        assert_eq!(
+            parse_header("x = 3, y = 2, rule = B36/S23"),
+            Ok((3, 2, Some(rules::highlife())))
+        );
+        assert_eq!(parse_header("x=4,y=5"), Ok((4, 5, None)));
+        assert_eq!(
+            parse_header("x = 3"),
+            Err(RleError::InvalidHeader("x = 3".to_string()))
+        );
+        assert!(matches!(
+            parse_header("x = 3, y = 3, rule = B9/S"),
+            Err(RleError::InvalidRule(_))
+        ));
+    }
+
+    #[test]
+    fn parse_header_ignores_bounded_grid_suffix() {
+        assert_eq!(
+            parse_header("x = 20, y = 20, rule = B3/S23:T20,20"),
+            Ok((20, 20, Some(rules::conways())))
+        );
+        assert_eq!(
+            parse_header("x = 8, y = 4, rule = B36/S23:P8,4"),
+            Ok((8, 4, Some(rules::highlife())))
+        );
+    }
+
+    #[test]
+    fn parse_header_reads_digits_only_survival_first_rule() {
+        assert_eq!(
+            parse_header("x = 3, y = 3, rule = 23/3"),
+            Ok((3, 3, Some(rules::conways())))
+        );
+        assert_eq!(
+            parse_header("x = 3, y = 3, rule = 23/36"),
+            Ok((3, 3, Some(rules::highlife())))
+        );
+        assert!(matches!(
+            parse_header("x = 3, y = 3, rule = 23/39"),
+            Err(RleError::InvalidRule(_))
+        ));
+    }
+
+    #[test]
+    fn decode_reads_runs_and_multiple_lines() {
+        let board =
+            decode(&["#C A comment", "x = 5, y = 4", "2o$", "2$", "3b", "2o!"].join("\n")).unwrap();
+
+        assert_eq!(
+            board.to_str('#', '_'),
+            ["##___", "_____", "_____", "___##"].join("\n")
+        );
+    }
+
+    #[test]
+    fn decode_round_trips_encode() {
+        use CellState::{Alive as O, Dead as B};
+
+        let rows: Vec<Vec<CellState>> = vec![vec![B, O, B], vec![B, B, B], vec![O, O, O]];
+        let rle = [
+            header(3, 3, &rules::conways()),
+            encode(rows.iter().map(Vec::as_slice)),
+        ]
+        .join("\n");
+
+        assert_eq!(decode(&rle), Ok(Board::from_grid(&rows)));
+    }
+
+    #[test]
+    fn decode_rejects_cells_outside_header_dimensions() {
+        assert_eq!(
+            decode("x = 2, y = 2\n3o!"),
+            Err(RleError::OutOfBounds(Coord::new(2, 0)))
+        );
+        assert_eq!(
+            decode("x = 2, y = 2\no2$o!"),
+            Err(RleError::OutOfBounds(Coord::new(0, 2)))
+        );
+    }
+
+    #[test]
+    fn decode_rejects_bad_input() {
+        assert_eq!(decode(""), Err(RleError::MissingHeader));
+        assert_eq!(decode("x = 0, y = 2\n!"), Err(RleError::InvalidDimensions));
+        assert_eq!(
+            decode("x = 2, y = 2\n2z!"),
+            Err(RleError::InvalidToken('z'))
+        );
+    }
+
+    #[test]
+    fn encode_skips_leading_empty_rows() {
+        use CellState::{Alive as O, Dead as B};
+
+        let rows: Vec<Vec<CellState>> = vec![vec![B, B], vec![B, B], vec![B, O]];
+
+        assert_eq!(encode(rows.iter().map(Vec::as_slice)), "2$bo!");
+    }
+
+    #[test]
+    fn dimensions_ignore_invalid_body() {
+        let rle = ["x = 400, y = 200", "this is not a pattern"].join("\n");
+
+        assert_eq!(dimensions(&rle), Ok((400, 200)));
+        assert!(decode(&rle).is_err());
+        assert_eq!(
+            dimensions("x = 0, y = 3\n!"),
+            Err(RleError::InvalidDimensions)
+        );
+        assert_eq!(dimensions("# Only a comment"), Err(RleError::MissingHeader));
+    }
+
+    #[test]
+    fn decode_rejects_overflowing_runs() {
+        assert_eq!(
+            decode("x = 2, y = 2\n99999999999999999999999o!"),
+            Err(RleError::Overflow)
+        );
+        assert_eq!(
+            decode("x = 2, y = 2\n18446744073709551615bb!"),
+            Err(RleError::Overflow)
+        );
+        assert_eq!(
+            decode("x = 2, y = 2\n18446744073709551615$$!"),
+            Err(RleError::Overflow)
+        );
+    }
+
+    #[test]
+    fn decode_rejects_huge_headers_before_allocating() {
+        assert_eq!(
+            decode("x = 18446744073709551615, y = 2\n!"),
+            Err(RleError::TooLarge)
+        );
+        assert_eq!(decode("x = 100000, y = 100000\n!"), Err(RleError::TooLarge));
+        assert_eq!(
+            dimensions("x = 100000, y = 100000\n!"),
+            Ok((100000, 100000))
+        );
+    }
+}
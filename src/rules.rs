@@ -1,5 +1,16 @@
+use crate::board::Board;
+use crate::coord::Coord;
+use crate::game::Game;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Largest number of cells in the box searched by [`still_lifes`]
+const MAX_STILL_LIFE_CELLS: usize = 16;
+
 /// Rules for a Game of Life
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Rules {
     /// Number of neighbour cells which must be alive for a cell to be born
     pub b: Vec<u8>,
@@ -43,6 +54,248 @@ impl Rules {
     pub fn birthed(&self, live_neighbours: u8) -> bool {
         self.b.contains(&live_neighbours)
     }
+
+    /// Empirically checks whether two rules behave the same, by stepping both of them one
+    /// generation on a number of random square boards and comparing the results. This is a
+    /// heuristic: rules which differ only in rare neighbourhoods may still be reported as equal
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::rules::{self, Rules};
+    ///
+    /// let conways = rules::conways();
+    /// let reordered = Rules {
+    ///     b: vec![3],
+    ///     s: vec![3, 2],
+    /// };
+    /// let seeds = Rules {
+    ///     b: vec![2],
+    ///     s: vec![],
+    /// };
+    ///
+    /// assert!(conways.behaves_like(&reordered, 8, 10, 1));
+    /// assert!(!conways.behaves_like(&seeds, 8, 10, 1));
+    /// ```
+    ///
+    /// # Panics
+    /// If the test size is 0
+    pub fn behaves_like(&self, other: &Rules, test_size: usize, samples: usize, seed: u64) -> bool {
+        (0..samples as u64).all(|sample| {
+            let board = Board::random(test_size, test_size, 0.5, seed.wrapping_add(sample));
+
+            let mut game = Game::new(board.clone(), self.clone());
+            let mut other_game = Game::new(board, other.clone());
+
+            game.advance_to_next_gen();
+            other_game.advance_to_next_gen();
+
+            game.board() == other_game.board()
+        })
+    }
+
+    /// Describes how the other rules differ from these, listing the neighbour counts the other
+    /// rules add (`+`) or remove (`-`) for birth and survival in ascending order
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::rules;
+    ///
+    /// assert_eq!(
+    ///     rules::conways().describe_difference(&rules::highlife()),
+    ///     "birth: +6; survival: unchanged"
+    /// );
+    /// assert_eq!(
+    ///     rules::conways().describe_difference(&rules::day_and_night()),
+    ///     "birth: +6 +7 +8; survival: -2 +4 +6 +7 +8"
+    /// );
+    /// ```
+    pub fn describe_difference(&self, other: &Rules) -> String {
+        let describe = |from: &Vec<u8>, to: &Vec<u8>| {
+            let changes: Vec<String> = (0..=8)
+                .filter_map(|count| match (from.contains(&count), to.contains(&count)) {
+                    (false, true) => Some(format!("+{}", count)),
+                    (true, false) => Some(format!("-{}", count)),
+                    _ => None,
+                })
+                .collect();
+
+            if changes.is_empty() {
+                "unchanged".to_string()
+            } else {
+                changes.join(" ")
+            }
+        };
+
+        format!(
+            "birth: {}; survival: {}",
+            describe(&self.b, &other.b),
+            describe(&self.s, &other.s)
+        )
+    }
+}
+
+impl fmt::Display for Rules {
+    /// Writes the rules in B/S notation, with the neighbour counts of each part in ascending
+    /// order and without duplicates
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::rules::{self, Rules};
+    ///
+    /// assert_eq!(rules::conways().to_string(), "B3/S23");
+    /// assert_eq!(Rules { b: vec![6, 3, 3], s: vec![] }.to_string(), "B36/S");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let digits = |counts: &Vec<u8>| {
+            let mut counts = counts.clone();
+            counts.sort_unstable();
+            counts.dedup();
+            counts.iter().map(u8::to_string).collect::<String>()
+        };
+
+        write!(f, "B{}/S{}", digits(&self.b), digits(&self.s))
+    }
+}
+
+impl FromStr for Rules {
+    type Err = ParseRulesError;
+
+    /// Parses rules in B/S notation, like `B3/S23`. The letters are case insensitive, and the
+    /// survival part may come first, like `S23/B3`
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::rules::{self, Rules};
+    ///
+    /// assert_eq!("B3/S23".parse::<Rules>(), Ok(rules::conways()));
+    /// assert_eq!("s23/b3".parse::<Rules>(), Ok(rules::conways()));
+    /// assert_eq!("B36/S".parse::<Rules>(), Ok(Rules { b: vec![3, 6], s: vec![] }));
+    ///
+    /// assert!("B3S23".parse::<Rules>().is_err());
+    /// assert!("B9/S23".parse::<Rules>().is_err());
+    /// ```
+    fn from_str(string: &str) -> Result<Rules, ParseRulesError> {
+        let (first, second) = string
+            .trim()
+            .split_once('/')
+            .ok_or(ParseRulesError::MissingSlash)?;
+
+        let counts = |part: &str| -> Result<(char, Vec<u8>), ParseRulesError> {
+            let mut chars = part.chars();
+            let prefix = match chars.next() {
+                Some(c) if c.eq_ignore_ascii_case(&'b') => 'b',
+                Some(c) if c.eq_ignore_ascii_case(&'s') => 's',
+                _ => return Err(ParseRulesError::InvalidPart(part.to_string())),
+            };
+
+            let counts = chars
+                .map(|c| match c.to_digit(10) {
+                    Some(count) if count <= 8 => Ok(count as u8),
+                    Some(count) => Err(ParseRulesError::CountTooHigh(count as u8)),
+                    None => Err(ParseRulesError::InvalidCharacter(c)),
+                })
+                .collect::<Result<Vec<u8>, ParseRulesError>>()?;
+
+            Ok((prefix, counts))
+        };
+
+        match (counts(first)?, counts(second)?) {
+            (('b', b), ('s', s)) | (('s', s), ('b', b)) => Ok(Rules { b, s }),
+            _ => Err(ParseRulesError::InvalidPart(second.to_string())),
+        }
+    }
+}
+
+/// Errors which can occur when parsing rules from B/S notation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseRulesError {
+    /// There is no slash separating the birth and survival parts
+    MissingSlash,
+    /// A part does not start with `B` or `S`, or both parts start with the same letter
+    InvalidPart(String),
+    /// A character which is not a digit
+    InvalidCharacter(char),
+    /// A neighbour count above 8, which no cell can have
+    CountTooHigh(u8),
+}
+
+impl fmt::Display for ParseRulesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseRulesError::MissingSlash => {
+                write!(f, "Missing '/' between the birth and survival parts")
+            }
+            ParseRulesError::InvalidPart(part) => write!(f, "Invalid rule part '{}'", part),
+            ParseRulesError::InvalidCharacter(c) => write!(f, "Invalid character '{}'", c),
+            ParseRulesError::CountTooHigh(count) => {
+                write!(f, "Neighbour count {} is higher than 8", count)
+            }
+        }
+    }
+}
+
+impl Error for ParseRulesError {}
+
+/// Rules for a Generations automaton, where live cells which do not survive go through a number of
+/// dying states before they are dead. Dying cells cannot be revived and do not count as live
+/// neighbours. `states` counts every state a cell can be in, including alive and dead, so rules
+/// with 2 states behave like ordinary [`Rules`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GenerationsRules {
+    /// Number of neighbour cells which must be alive for a cell to be born
+    pub b: Vec<u8>,
+    /// Number of neighbour cells which must be alive for a cell to stay alive
+    pub s: Vec<u8>,
+    /// Number of states a cell can be in, including alive and dead
+    pub states: u8,
+}
+
+impl GenerationsRules {
+    /// Checks whether the rules says a live cell with the given number of live neighbours stays
+    /// alive in the next generation
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::rules;
+    ///
+    /// let rules = rules::star_wars();
+    ///
+    /// assert!(!rules.survives(2));
+    /// assert!(rules.survives(3));
+    /// assert!(rules.survives(5));
+    /// assert!(!rules.survives(6));
+    /// ```
+    pub fn survives(&self, live_neighbours: u8) -> bool {
+        self.s.contains(&live_neighbours)
+    }
+
+    /// Checks whether the rules says a dead cell with the given number of live neighbours is born
+    /// in the next generation
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::rules;
+    ///
+    /// let rules = rules::brians_brain();
+    ///
+    /// assert!(!rules.birthed(1));
+    /// assert!(rules.birthed(2));
+    /// assert!(!rules.birthed(3));
+    /// ```
+    pub fn birthed(&self, live_neighbours: u8) -> bool {
+        self.b.contains(&live_neighbours)
+    }
+}
+
+impl From<Rules> for GenerationsRules {
+    fn from(rules: Rules) -> GenerationsRules {
+        GenerationsRules {
+            b: rules.b,
+            s: rules.s,
+            states: 2,
+        }
+    }
 }
 
 /// Rules for the original Conway's Game of Life
@@ -52,3 +305,437 @@ pub fn conways() -> Rules {
         s: vec![2, 3],
     }
 }
+
+/// Rules for HighLife (B36/S23), which is like Conway's Game of Life but has a replicator
+pub fn highlife() -> Rules {
+    Rules {
+        b: vec![3, 6],
+        s: vec![2, 3],
+    }
+}
+
+/// Rules for Day & Night (B3678/S34678), where live and dead cells behave symmetrically
+pub fn day_and_night() -> Rules {
+    Rules {
+        b: vec![3, 6, 7, 8],
+        s: vec![3, 4, 6, 7, 8],
+    }
+}
+
+/// Rules for Seeds (B2/S), where every live cell dies in the next generation
+pub fn seeds() -> Rules {
+    Rules {
+        b: vec![2],
+        s: vec![],
+    }
+}
+
+/// Rules for Life without Death (B3/S012345678), where live cells never die
+pub fn life_without_death() -> Rules {
+    Rules {
+        b: vec![3],
+        s: vec![0, 1, 2, 3, 4, 5, 6, 7, 8],
+    }
+}
+
+/// Rules for Replicator (B1357/S1357), where every pattern is eventually copied
+pub fn replicator() -> Rules {
+    Rules {
+        b: vec![1, 3, 5, 7],
+        s: vec![1, 3, 5, 7],
+    }
+}
+
+/// Generations rules for Brian's Brain (B2/S/3), where every live cell dies after one generation
+/// of dying
+pub fn brians_brain() -> GenerationsRules {
+    GenerationsRules {
+        b: vec![2],
+        s: vec![],
+        states: 3,
+    }
+}
+
+/// Generations rules for Star Wars (B2/S345/4)
+pub fn star_wars() -> GenerationsRules {
+    GenerationsRules {
+        b: vec![2],
+        s: vec![3, 4, 5],
+        states: 4,
+    }
+}
+
+/// Finds all still lifes under the given rules which fit within a box of the given size, by
+/// trying every configuration of the box. Each still life is returned once, trimmed to its live
+/// cells, in a single orientation: rotations and reflections of a still life are not repeated.
+/// Still lifes made of several separate parts, like two blocks side by side, are included
+///
+/// # Examples
+/// ```
+/// use game_of_life::{patterns, rules};
+///
+/// let still_lifes = rules::still_lifes(&rules::conways(), 3, 3);
+///
+/// assert!(still_lifes.contains(&patterns::block()));
+/// // Block, tub, ship and boat
+/// assert_eq!(still_lifes.len(), 4);
+/// ```
+///
+/// # Panics
+/// If the box has more than 16 cells, as the search would take too long
+pub fn still_lifes(rules: &Rules, max_width: usize, max_height: usize) -> Vec<Board> {
+    let cells = max_width * max_height;
+    if cells > MAX_STILL_LIFE_CELLS {
+        panic!(
+            "Cannot search for still lifes in more than {} cells",
+            MAX_STILL_LIFE_CELLS
+        );
+    }
+
+    let mut still_lifes: Vec<Board> = Vec::new();
+
+    for configuration in 1u32..(1 << cells) {
+        // Leave a dead border around the box so cells can be born around the pattern without
+        // wrapping around to the other side
+        let mut board = Board::new(max_width + 2, max_height + 2);
+        for i in (0..cells).filter(|i| configuration & (1 << i) != 0) {
+            board.revive_cell(&Coord::new(i % max_width + 1, i / max_width + 1));
+        }
+
+        let game = Game::new(board, rules.clone());
+        if !game.next_gen_toggles().is_empty() {
+            continue;
+        }
+
+        if let Some((_, still_life)) = game.board().toroidal_crop() {
+            let still_life = canonical_orientation(&still_life);
+            if !still_lifes.contains(&still_life) {
+                still_lifes.push(still_life);
+            }
+        }
+    }
+
+    still_lifes
+}
+
+/// Picks one of the eight rotations and reflections of a board, always the same one for every
+/// orientation of the same pattern
+fn canonical_orientation(board: &Board) -> Board {
+    let mut orientations = Vec::with_capacity(8);
+    let mut rotated = board.clone();
+    for _ in 0..4 {
+        orientations.push(rotated.flip_horizontal());
+        rotated = rotated.rotate_cw();
+        orientations.push(rotated.clone());
+    }
+
+    orientations
+        .into_iter()
+        .min_by_key(|orientation| orientation.to_str('#', '_'))
+        .unwrap()
+}
+
+/// Measures the mean density of live cells left after random soups have settled. Each soup is a
+/// random square board of the given size where half the cells are alive, which is advanced until
+/// it stops changing or the given number of generations have passed. Oscillators and spaceships
+/// never stop changing, so they are measured after `settle_gens` generations. The same seed always
+/// gives the same result
+///
+/// # Examples
+/// ```
+/// use game_of_life::rules;
+///
+/// let density = rules::ash_density(&rules::conways(), 16, 4, 200, 1);
+///
+/// assert!((0.0..=1.0).contains(&density));
+/// ```
+///
+/// # Panics
+/// If the size or the number of soups is 0
+pub fn ash_density(rules: &Rules, size: usize, soups: usize, settle_gens: usize, seed: u64) -> f64 {
+    if size == 0 || soups == 0 {
+        panic!("Cannot measure ash density without any cells");
+    }
+
+    let total: f64 = (0..soups as u64)
+        .map(|soup| {
+            let board = Board::random(size, size, 0.5, seed.wrapping_add(soup));
+            let mut game = Game::new(board, rules.clone());
+
+            for _ in 0..settle_gens {
+                if !game.step() {
+                    break;
+                }
+            }
+
+            game.board().population() as f64 / (size * size) as f64
+        })
+        .sum();
+
+    total / soups as f64
+}
+
+/// Advances a row-major buffer of cells one generation in place, without building a board. Cells
+/// which are not 0 are alive, and come out as 1 if they are still alive in the next generation.
+/// With `toroidal`, the edges of the buffer wrap around, otherwise everything outside it is dead.
+/// This suits frontends keeping the cells in a flat buffer, like a browser drawing them from
+/// WebAssembly memory
+///
+/// # Examples
+/// ```
+/// use game_of_life::rules;
+///
+/// let mut cells = [
+///     0, 0, 0, 0, 0,
+///     0, 0, 1, 0, 0,
+///     0, 0, 1, 0, 0,
+///     0, 0, 1, 0, 0,
+///     0, 0, 0, 0, 0,
+/// ];
+///
+/// rules::step_buffer(&mut cells, 5, 5, &rules::conways(), true);
+///
+/// assert_eq!(cells, [
+///     0, 0, 0, 0, 0,
+///     0, 0, 0, 0, 0,
+///     0, 1, 1, 1, 0,
+///     0, 0, 0, 0, 0,
+///     0, 0, 0, 0, 0,
+/// ]);
+/// ```
+///
+/// # Panics
+/// If the length of the buffer is not `width * height`
+pub fn step_buffer(cells: &mut [u8], width: usize, height: usize, rules: &Rules, toroidal: bool) {
+    assert_eq!(
+        cells.len(),
+        width * height,
+        "Buffer must have width * height cells"
+    );
+
+    const OFFSETS: [(isize, isize); 8] = [
+        (-1, -1),
+        (0, -1),
+        (1, -1),
+        (-1, 0),
+        (1, 0),
+        (-1, 1),
+        (0, 1),
+        (1, 1),
+    ];
+
+    let current = cells.to_vec();
+    let is_alive = |x: usize, dx: isize, y: usize, dy: isize| -> bool {
+        let (x, y) = (x as isize + dx, y as isize + dy);
+        let (width, height) = (width as isize, height as isize);
+
+        if toroidal {
+            let (x, y) = (x.rem_euclid(width), y.rem_euclid(height));
+            current[(y * width + x) as usize] != 0
+        } else {
+            (0..width).contains(&x)
+                && (0..height).contains(&y)
+                && current[(y * width + x) as usize] != 0
+        }
+    };
+
+    for y in 0..height {
+        for x in 0..width {
+            let live_neighbours = OFFSETS
+                .iter()
+                .filter(|(dx, dy)| is_alive(x, *dx, y, *dy))
+                .count() as u8;
+
+            let alive = if current[y * width + x] != 0 {
+                rules.survives(live_neighbours)
+            } else {
+                rules.birthed(live_neighbours)
+            };
+
+            cells[y * width + x] = alive as u8;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::boundary::Boundary;
+    use crate::cell_state::CellState;
+
+    #[test]
+    fn conways_behaves_like_itself() {
+        assert!(conways().behaves_like(&conways(), 10, 20, 0));
+    }
+
+    #[test]
+    fn conways_does_not_behave_like_highlife() {
+        assert!(!conways().behaves_like(&highlife(), 16, 20, 0));
+    }
+
+    #[test]
+    fn still_lifes_in_2x2_box_under_conways_is_the_block() {
+        let still_lifes = still_lifes(&conways(), 2, 2);
+
+        assert_eq!(still_lifes, vec![crate::patterns::block()]);
+    }
+
+    #[test]
+    fn still_lifes_are_stable() {
+        for still_life in still_lifes(&conways(), 4, 3) {
+            let mut board = Board::new(still_life.width() + 2, still_life.height() + 2);
+            for coord in still_life.cell_coords() {
+                if still_life.get_cell_state(coord) == &CellState::Alive {
+                    board.revive_cell(&Coord::new(coord.x + 1, coord.y + 1));
+                }
+            }
+
+            assert!(Game::new(board, conways()).next_gen_toggles().is_empty());
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn still_lifes_in_too_large_box_panics() {
+        still_lifes(&conways(), 5, 5);
+    }
+
+    #[test]
+    fn ash_density_is_deterministic_for_a_fixed_seed() {
+        let density = ash_density(&conways(), 16, 5, 300, 42);
+
+        assert_eq!(density, ash_density(&conways(), 16, 5, 300, 42));
+        assert!(density > 0.0 && density < 0.5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn ash_density_panics_without_soups() {
+        ash_density(&conways(), 16, 0, 300, 42);
+    }
+
+    #[test]
+    fn from_str_parses_either_order_and_case() {
+        let highlife = Rules {
+            b: vec![3, 6],
+            s: vec![2, 3],
+        };
+
+        assert_eq!("B36/S23".parse(), Ok(highlife.clone()));
+        assert_eq!("b36/s23".parse(), Ok(highlife.clone()));
+        assert_eq!("S23/B36".parse(), Ok(highlife));
+    }
+
+    #[test]
+    fn from_str_rejects_malformed_rules() {
+        assert_eq!("B3S23".parse::<Rules>(), Err(ParseRulesError::MissingSlash));
+        assert_eq!(
+            "B3/S2x".parse::<Rules>(),
+            Err(ParseRulesError::InvalidCharacter('x'))
+        );
+        assert_eq!(
+            "B3/S29".parse::<Rules>(),
+            Err(ParseRulesError::CountTooHigh(9))
+        );
+        assert_eq!(
+            "B3/B23".parse::<Rules>(),
+            Err(ParseRulesError::InvalidPart("B23".to_string()))
+        );
+        assert_eq!(
+            "3/23".parse::<Rules>(),
+            Err(ParseRulesError::InvalidPart("3".to_string()))
+        );
+    }
+
+    #[test]
+    fn display_round_trips_through_from_str() {
+        let rules = Rules {
+            b: vec![6, 3],
+            s: vec![3, 2, 3],
+        };
+
+        assert_eq!(rules.to_string(), "B36/S23");
+        assert_eq!(
+            rules.to_string().parse::<Rules>().unwrap().to_string(),
+            rules.to_string()
+        );
+        assert_eq!(conways().to_string().parse(), Ok(conways()));
+    }
+
+    #[test]
+    fn named_rules_have_expected_notation() {
+        assert_eq!(highlife().to_string(), "B36/S23");
+        assert_eq!(day_and_night().to_string(), "B3678/S34678");
+        assert_eq!(seeds().to_string(), "B2/S");
+        assert_eq!(life_without_death().to_string(), "B3/S012345678");
+        assert_eq!(replicator().to_string(), "B1357/S1357");
+    }
+
+    #[test]
+    fn describe_difference_between_conways_and_highlife() {
+        let description = conways().describe_difference(&highlife());
+
+        assert!(description.contains("+6"));
+        assert_eq!(description, "birth: +6; survival: unchanged");
+        assert_eq!(
+            highlife().describe_difference(&conways()),
+            "birth: -6; survival: unchanged"
+        );
+    }
+
+    #[test]
+    fn describe_difference_of_same_rules_is_unchanged() {
+        assert_eq!(
+            conways().describe_difference(&"S32/B3".parse().unwrap()),
+            "birth: unchanged; survival: unchanged"
+        );
+    }
+
+    #[test]
+    fn step_buffer_matches_game_step() {
+        for (boundary, toroidal) in [(Boundary::Toroidal, true), (Boundary::Dead, false)] {
+            let mut board = Board::random(12, 9, 0.4, 17);
+            board.set_boundary(boundary);
+
+            let mut cells: Vec<u8> = board
+                .cell_coords()
+                .iter()
+                .map(|c| (board.get_cell_state(c) == &CellState::Alive) as u8)
+                .collect();
+            let mut game = Game::new(board, highlife());
+
+            for _ in 0..5 {
+                step_buffer(&mut cells, 12, 9, &highlife(), toroidal);
+                game.advance_to_next_gen();
+
+                let expected: Vec<u8> = game
+                    .board()
+                    .cell_coords()
+                    .iter()
+                    .map(|c| (game.board().get_cell_state(c) == &CellState::Alive) as u8)
+                    .collect();
+
+                assert_eq!(cells, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn still_lifes_are_listed_in_one_orientation_each() {
+        let still_lifes = still_lifes(&conways(), 4, 3);
+
+        for (i, still_life) in still_lifes.iter().enumerate() {
+            let mut rotated = still_life.clone();
+            for _ in 0..4 {
+                for orientation in [rotated.clone(), rotated.flip_horizontal()] {
+                    let index = still_lifes.iter().position(|s| s == &orientation);
+                    assert!(index.is_none() || index == Some(i));
+                }
+                rotated = rotated.rotate_cw();
+            }
+        }
+
+        // Block, snake, tub, boat, ship, aircraft carrier and beehive
+        assert_eq!(still_lifes.len(), 7);
+    }
+}
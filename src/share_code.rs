@@ -0,0 +1,412 @@
+use crate::board::Board;
+use crate::cell_state::CellState;
+use crate::coord::Coord;
+use std::error::Error;
+use std::fmt;
+
+/// Characters of the URL safe base64 alphabet
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Largest number of cells in a decoded board. Compression lets a short code describe a huge
+/// board, so this keeps a code from allocating more than a 4096 by 4096 board
+const MAX_DECODED_CELLS: usize = 1 << 24;
+
+/// Errors which can occur when decoding a share code
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The code contains a character which is not in the URL safe base64 alphabet
+    InvalidCharacter(char),
+    /// The code ends before the whole board is decoded
+    Truncated,
+    /// The code describes a board with no rows or no columns
+    InvalidDimensions,
+    /// The code describes a board with more than 2^24 cells, like a 4096 by 4096 board
+    TooLarge,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodeError::InvalidCharacter(c) => write!(f, "Invalid character '{}' in code", c),
+            DecodeError::Truncated => write!(f, "Code is truncated"),
+            DecodeError::InvalidDimensions => write!(f, "Code has invalid dimensions"),
+            DecodeError::TooLarge => write!(f, "Code has more than {} cells", MAX_DECODED_CELLS),
+        }
+    }
+}
+
+impl Error for DecodeError {}
+
+/// Encodes rows of cells as a share code: the dimensions and the cells packed as bits, compressed
+/// and encoded as URL safe base64
+pub fn encode<'a, I: IntoIterator<Item = &'a [CellState]>>(
+    width: usize,
+    height: usize,
+    rows: I,
+) -> String {
+    let mut bytes = Vec::new();
+    write_varint(&mut bytes, width);
+    write_varint(&mut bytes, height);
+
+    let mut bits = BitWriter::default();
+    for row in rows {
+        for cell_state in row {
+            bits.push(cell_state == &CellState::Alive);
+        }
+    }
+    bytes.extend(bits.finish());
+
+    base64_encode(&compress(&bytes))
+}
+
+/// Decodes a share code into a board. Codes describing more than `MAX_DECODED_CELLS` cells are
+/// rejected before the board is allocated, and the cells are only decompressed as far as the
+/// dimensions allow, so a code cannot expand to more bytes than its board needs
+pub fn decode(code: &str) -> Result<Board, DecodeError> {
+    let compressed = base64_decode(code)?;
+    let mut bytes = decompress(&compressed);
+
+    let width = read_varint(&mut bytes)?;
+    let height = read_varint(&mut bytes)?;
+    if width == 0 || height == 0 {
+        return Err(DecodeError::InvalidDimensions);
+    }
+
+    let cells = width
+        .checked_mul(height)
+        .ok_or(DecodeError::InvalidDimensions)?;
+    if cells > MAX_DECODED_CELLS {
+        return Err(DecodeError::TooLarge);
+    }
+    let max_len = cells / 8 + 1;
+    let bits = bytes
+        .by_ref()
+        .take(max_len)
+        .collect::<Result<Vec<u8>, DecodeError>>()?;
+    if bits.len() * 8 < cells {
+        return Err(DecodeError::Truncated);
+    }
+    if bytes.next().is_some() {
+        return Err(DecodeError::TooLarge);
+    }
+
+    let mut board = Board::new(width, height);
+    for i in 0..cells {
+        if bits[i / 8] & (0x80 >> (i % 8)) != 0 {
+            board.revive_cell(&Coord::new(i % width, i / width));
+        }
+    }
+
+    Ok(board)
+}
+
+/// Packs bits into bytes, most significant bit first
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    len: usize,
+}
+
+impl BitWriter {
+    fn push(&mut self, bit: bool) {
+        if self.len % 8 == 0 {
+            self.bytes.push(0);
+        }
+        if bit {
+            *self.bytes.last_mut().unwrap() |= 0x80 >> (self.len % 8);
+        }
+        self.len += 1;
+    }
+
+    fn finish(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+/// Writes a number as a variable length integer, 7 bits per byte with the high bit set on all
+/// but the last byte
+fn write_varint(bytes: &mut Vec<u8>, mut n: usize) {
+    while n >= 0x80 {
+        bytes.push((n & 0x7f) as u8 | 0x80);
+        n >>= 7;
+    }
+    bytes.push(n as u8);
+}
+
+/// Reads a variable length integer written by `write_varint`
+fn read_varint<I: Iterator<Item = Result<u8, DecodeError>>>(
+    bytes: &mut I,
+) -> Result<usize, DecodeError> {
+    let mut n: usize = 0;
+
+    for shift in (0..usize::BITS).step_by(7) {
+        let byte = bytes.next().ok_or(DecodeError::Truncated)??;
+        n |= ((byte & 0x7f) as usize)
+            .checked_shl(shift)
+            .ok_or(DecodeError::InvalidDimensions)?;
+
+        if byte & 0x80 == 0 {
+            return Ok(n);
+        }
+    }
+
+    Err(DecodeError::InvalidDimensions)
+}
+
+/// Compresses bytes with PackBits run length encoding. A header byte `h` is followed either by
+/// `h + 1` literal bytes if `h < 128`, or by one byte to repeat `257 - h` times otherwise
+fn compress(bytes: &[u8]) -> Vec<u8> {
+    let mut compressed = Vec::new();
+    let mut literals: Vec<u8> = Vec::new();
+    let mut i = 0;
+
+    let flush = |compressed: &mut Vec<u8>, literals: &mut Vec<u8>| {
+        if !literals.is_empty() {
+            compressed.push((literals.len() - 1) as u8);
+            compressed.append(literals);
+        }
+    };
+
+    while i < bytes.len() {
+        let run = bytes[i..]
+            .iter()
+            .take(128)
+            .take_while(|&&b| b == bytes[i])
+            .count();
+
+        if run >= 3 {
+            flush(&mut compressed, &mut literals);
+            compressed.push((257 - run) as u8);
+            compressed.push(bytes[i]);
+            i += run;
+        } else {
+            literals.push(bytes[i]);
+            if literals.len() == 128 {
+                flush(&mut compressed, &mut literals);
+            }
+            i += 1;
+        }
+    }
+    flush(&mut compressed, &mut literals);
+
+    compressed
+}
+
+/// Decompresses bytes compressed by `compress`, one byte at a time so the caller can stop before
+/// a short input expands into a huge output
+fn decompress(bytes: &[u8]) -> Decompress<'_> {
+    Decompress {
+        bytes,
+        literals: &[],
+        byte: 0,
+        repeats: 0,
+    }
+}
+
+/// Iterator over the decompressed bytes of PackBits compressed bytes. See `decompress`
+struct Decompress<'a> {
+    /// Compressed bytes not read yet
+    bytes: &'a [u8],
+    /// Literal bytes left of the current block
+    literals: &'a [u8],
+    /// Byte repeated by the current run
+    byte: u8,
+    /// Number of repeats left of the current run
+    repeats: usize,
+}
+
+impl Iterator for Decompress<'_> {
+    type Item = Result<u8, DecodeError>;
+
+    fn next(&mut self) -> Option<Result<u8, DecodeError>> {
+        loop {
+            if let Some((&literal, rest)) = self.literals.split_first() {
+                self.literals = rest;
+                return Some(Ok(literal));
+            }
+
+            if self.repeats > 0 {
+                self.repeats -= 1;
+                return Some(Ok(self.byte));
+            }
+
+            let (&header, rest) = self.bytes.split_first()?;
+            let header = header as usize;
+
+            if header < 128 {
+                let Some(literals) = rest.get(..=header) else {
+                    self.bytes = &[];
+                    return Some(Err(DecodeError::Truncated));
+                };
+                self.literals = literals;
+                self.bytes = &rest[header + 1..];
+            } else {
+                let Some((&byte, rest)) = rest.split_first() else {
+                    self.bytes = &[];
+                    return Some(Err(DecodeError::Truncated));
+                };
+                self.byte = byte;
+                self.repeats = 257 - header;
+                self.bytes = rest;
+            }
+        }
+    }
+}
+
+/// Encodes bytes as URL safe base64 without padding
+fn base64_encode(bytes: &[u8]) -> String {
+    let mut encoded = String::new();
+
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+
+        for i in 0..=chunk.len() {
+            encoded.push(BASE64_ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+    }
+
+    encoded
+}
+
+/// Decodes URL safe base64 without padding
+fn base64_decode(code: &str) -> Result<Vec<u8>, DecodeError> {
+    let values = code
+        .chars()
+        .map(|c| {
+            BASE64_ALPHABET
+                .iter()
+                .position(|&a| a as char == c)
+                .map(|v| v as u32)
+                .ok_or(DecodeError::InvalidCharacter(c))
+        })
+        .collect::<Result<Vec<u32>, DecodeError>>()?;
+
+    let mut bytes = Vec::new();
+    for chunk in values.chunks(4) {
+        if chunk.len() == 1 {
+            return Err(DecodeError::Truncated);
+        }
+
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &v)| n | v << (18 - 6 * i));
+
+        for i in 0..chunk.len() - 1 {
+            bytes.push((n >> (16 - 8 * i)) as u8);
+        }
+    }
+
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn base64_round_trips() {
+        for len in 0..10 {
+            let bytes: Vec<u8> = (0..len).map(|i| (i * 37 + 250) as u8).collect();
+
+            assert_eq!(base64_decode(&base64_encode(&bytes)).unwrap(), bytes);
+        }
+    }
+
+    #[test]
+    fn base64_encodes_known_value() {
+        assert_eq!(base64_encode(b"Man"), "TWFu");
+        assert_eq!(base64_encode(&[0xfb, 0xff]), "-_8");
+    }
+
+    #[test]
+    fn compression_round_trips() {
+        let mut bytes = vec![1, 2, 3, 3, 3, 3, 4];
+        bytes.extend(vec![0; 300]);
+        bytes.extend((0..200).map(|i| i as u8));
+
+        let compressed = compress(&bytes);
+
+        assert!(compressed.len() < bytes.len());
+        assert_eq!(
+            decompress(&compressed).collect::<Result<Vec<u8>, _>>(),
+            Ok(bytes)
+        );
+    }
+
+    #[test]
+    fn varint_round_trips() {
+        for n in [0, 1, 127, 128, 300, 1 << 20] {
+            let mut bytes = Vec::new();
+            write_varint(&mut bytes, n);
+
+            assert_eq!(read_varint(&mut bytes.into_iter().map(Ok)).unwrap(), n);
+        }
+    }
+
+    #[test]
+    fn decode_rejects_invalid_characters() {
+        assert_eq!(decode("ab+c"), Err(DecodeError::InvalidCharacter('+')));
+    }
+
+    #[test]
+    fn decode_rejects_truncated_codes() {
+        let code = encode(
+            2,
+            2,
+            [
+                [CellState::Alive, CellState::Dead],
+                [CellState::Dead, CellState::Alive],
+            ]
+            .iter()
+            .map(|row| &row[..]),
+        );
+
+        assert!(decode(&code[..code.len() - 2]).is_err());
+    }
+
+    #[test]
+    fn decode_rejects_huge_boards_before_allocating() {
+        let mut bytes = Vec::new();
+        write_varint(&mut bytes, 100_000);
+        write_varint(&mut bytes, 100_000);
+        // A few compressed runs stand in for a body far too short for the claimed size
+        bytes.extend([0; 64]);
+
+        assert_eq!(
+            decode(&base64_encode(&compress(&bytes))),
+            Err(DecodeError::TooLarge)
+        );
+    }
+
+    #[test]
+    fn decode_stops_decompressing_past_the_size_of_the_board() {
+        let mut bytes = Vec::new();
+        write_varint(&mut bytes, 4);
+        write_varint(&mut bytes, 4);
+        // A 4 by 4 board needs 2 bytes, but the runs expand to a million
+        bytes.extend(vec![0; 1_000_000]);
+
+        let compressed = compress(&bytes);
+        assert!(compressed.len() < 20_000);
+
+        assert_eq!(
+            decode(&base64_encode(&compressed)),
+            Err(DecodeError::TooLarge)
+        );
+    }
+
+    #[test]
+    fn decode_accepts_body_of_exactly_the_board_size() {
+        let rows = [vec![CellState::Alive; 8], vec![CellState::Dead; 8]];
+        let code = encode(8, 2, rows.iter().map(|row| &row[..]));
+
+        let board = decode(&code).unwrap();
+
+        assert_eq!(board.population(), 8);
+    }
+}
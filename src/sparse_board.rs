@@ -0,0 +1,409 @@
+use crate::board::Board;
+use crate::boundary::Boundary;
+use crate::cell_state::CellState;
+use crate::coord::{Coord, NEIGHBOUR_OFFSETS};
+use crate::neighbourhood::Neighbourhood;
+use crate::rules::Rules;
+use std::collections::{HashMap, HashSet};
+
+/// A toroidal board which only stores its live cells. It takes memory and time in proportion to
+/// the number of live cells rather than the size of the board, so it suits huge boards with little
+/// on them, like a single glider crossing a million cells
+///
+/// # Examples
+/// ```
+/// use game_of_life::{SparseBoard, Coord, rules};
+///
+/// let mut board = SparseBoard::new(1000, 1000);
+/// for (x, y) in [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)] {
+///     board.revive_cell(&Coord::new(x, y));
+/// }
+///
+/// for _ in 0..4 {
+///     board.advance(&rules::conways());
+/// }
+///
+/// // The glider has moved one cell down and to the right
+/// assert_eq!(board.population(), 5);
+/// assert!(board.is_alive(&Coord::new(3, 3)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseBoard {
+    width: usize,
+    height: usize,
+    /// Coordinates of the live cells. Every other cell is dead
+    live: HashSet<Coord>,
+}
+
+impl SparseBoard {
+    /// Creates a new board with the desired size, initializing all cells as dead
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::SparseBoard;
+    ///
+    /// let board = SparseBoard::new(1000, 1000);
+    ///
+    /// assert_eq!(board.population(), 0);
+    /// ```
+    ///
+    /// # Panics
+    /// If width or height is 0
+    pub fn new(width: usize, height: usize) -> SparseBoard {
+        if width == 0 || height == 0 {
+            panic!("Width and height must be at least 1");
+        }
+
+        SparseBoard {
+            width,
+            height,
+            live: HashSet::new(),
+        }
+    }
+
+    /// Creates a sparse board with the same size and live cells as a board. Sparse boards always
+    /// wrap around and use the Moore neighbourhood, so only boards with the same settings can be
+    /// converted
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{Board, SparseBoard};
+    ///
+    /// let board = Board::from_str(&vec![
+    ///     "#_",
+    ///     "_#",
+    /// ].join("\n"), '#');
+    ///
+    /// let sparse = SparseBoard::from_board(&board);
+    ///
+    /// assert_eq!(sparse.population(), 2);
+    /// assert_eq!(sparse.to_board(), board);
+    /// ```
+    ///
+    /// # Panics
+    /// If the board does not have a toroidal boundary and the Moore neighbourhood
+    pub fn from_board(board: &Board) -> SparseBoard {
+        if board.boundary() != Boundary::Toroidal || board.neighbourhood() != Neighbourhood::Moore {
+            panic!("Only toroidal boards with the Moore neighbourhood can be made sparse");
+        }
+
+        SparseBoard {
+            width: board.width(),
+            height: board.height(),
+            live: board.live_cells().cloned().collect(),
+        }
+    }
+
+    /// Creates a dense board with the same size and live cells
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{SparseBoard, Coord};
+    ///
+    /// let mut sparse = SparseBoard::new(3, 2);
+    /// sparse.revive_cell(&Coord::new(1, 1));
+    ///
+    /// assert_eq!(sparse.to_board().to_str('#', '_'), vec![
+    ///     "___",
+    ///     "_#_",
+    /// ].join("\n"));
+    /// ```
+    pub fn to_board(&self) -> Board {
+        let mut board = Board::new(self.width, self.height);
+
+        for coord in &self.live {
+            board.revive_cell(coord);
+        }
+
+        board
+    }
+
+    /// Gets the width of the board
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::SparseBoard;
+    ///
+    /// assert_eq!(SparseBoard::new(4, 3).width(), 4);
+    /// ```
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Gets the height of the board
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::SparseBoard;
+    ///
+    /// assert_eq!(SparseBoard::new(4, 3).height(), 3);
+    /// ```
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Counts the live cells on the board
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{SparseBoard, Coord};
+    ///
+    /// let mut board = SparseBoard::new(4, 4);
+    /// board.revive_cell(&Coord::new(0, 0));
+    /// board.revive_cell(&Coord::new(3, 3));
+    ///
+    /// assert_eq!(board.population(), 2);
+    /// ```
+    pub fn population(&self) -> usize {
+        self.live.len()
+    }
+
+    /// Iterates over the coordinates of the live cells on the board, in no particular order
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{SparseBoard, Coord};
+    ///
+    /// let mut board = SparseBoard::new(4, 4);
+    /// board.revive_cell(&Coord::new(2, 1));
+    ///
+    /// assert_eq!(board.live_cells().collect::<Vec<_>>(), vec![&Coord::new(2, 1)]);
+    /// ```
+    pub fn live_cells(&self) -> impl Iterator<Item = &Coord> {
+        self.live.iter()
+    }
+
+    /// Checks whether the cell at the given coordinate is alive
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{SparseBoard, Coord};
+    ///
+    /// let mut board = SparseBoard::new(4, 4);
+    /// board.revive_cell(&Coord::new(2, 1));
+    ///
+    /// assert!(board.is_alive(&Coord::new(2, 1)));
+    /// assert!(!board.is_alive(&Coord::new(1, 2)));
+    /// ```
+    pub fn is_alive(&self, coord: &Coord) -> bool {
+        self.live.contains(coord)
+    }
+
+    /// Gets the state of the cell at the given coordinate
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{SparseBoard, Coord, CellState};
+    ///
+    /// let mut board = SparseBoard::new(4, 4);
+    /// board.revive_cell(&Coord::new(2, 1));
+    ///
+    /// assert_eq!(board.get_cell_state(&Coord::new(2, 1)), CellState::Alive);
+    /// assert_eq!(board.get_cell_state(&Coord::new(0, 0)), CellState::Dead);
+    /// ```
+    pub fn get_cell_state(&self, coord: &Coord) -> CellState {
+        if self.is_alive(coord) {
+            CellState::Alive
+        } else {
+            CellState::Dead
+        }
+    }
+
+    /// Revives the cell at the given coordinate, making sure it is alive
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{SparseBoard, Coord};
+    ///
+    /// let mut board = SparseBoard::new(4, 4);
+    /// board.revive_cell(&Coord::new(1, 1));
+    /// board.revive_cell(&Coord::new(1, 1));
+    ///
+    /// assert_eq!(board.population(), 1);
+    /// ```
+    ///
+    /// # Panics
+    /// If the coordinate is outside the board
+    pub fn revive_cell(&mut self, coord: &Coord) {
+        self.check_bounds(coord);
+        self.live.insert(coord.clone());
+    }
+
+    /// Kills the cell at the given coordinate, making sure it is dead
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{SparseBoard, Coord};
+    ///
+    /// let mut board = SparseBoard::new(4, 4);
+    /// board.revive_cell(&Coord::new(1, 1));
+    /// board.kill_cell(&Coord::new(1, 1));
+    /// board.kill_cell(&Coord::new(1, 1));
+    ///
+    /// assert_eq!(board.population(), 0);
+    /// ```
+    ///
+    /// # Panics
+    /// If the coordinate is outside the board
+    pub fn kill_cell(&mut self, coord: &Coord) {
+        self.check_bounds(coord);
+        self.live.remove(coord);
+    }
+
+    /// Toggles the state of the cell at the given coordinate
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{SparseBoard, Coord};
+    ///
+    /// let mut board = SparseBoard::new(4, 4);
+    ///
+    /// board.toggle_cell(&Coord::new(1, 1));
+    /// assert!(board.is_alive(&Coord::new(1, 1)));
+    ///
+    /// board.toggle_cell(&Coord::new(1, 1));
+    /// assert!(!board.is_alive(&Coord::new(1, 1)));
+    /// ```
+    ///
+    /// # Panics
+    /// If the coordinate is outside the board
+    pub fn toggle_cell(&mut self, coord: &Coord) {
+        self.check_bounds(coord);
+        if !self.live.remove(coord) {
+            self.live.insert(coord.clone());
+        }
+    }
+
+    /// Panics if the coordinate is outside the board
+    fn check_bounds(&self, coord: &Coord) {
+        if coord.x >= self.width || coord.y >= self.height {
+            panic!("Coordinate {:?} is outside the board", coord);
+        }
+    }
+
+    /// Advances the board to the next generation under the given rules, with the edges wrapping
+    /// around. Only the live cells and their neighbours are looked at, unless the rules give birth
+    /// to cells with no live neighbours, in which case every cell has to be checked
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::{SparseBoard, Coord, rules};
+    ///
+    /// // A blinker across the top and bottom edges
+    /// let mut board = SparseBoard::new(5, 5);
+    /// for y in [4, 0, 1] {
+    ///     board.revive_cell(&Coord::new(2, y));
+    /// }
+    ///
+    /// board.advance(&rules::conways());
+    ///
+    /// assert_eq!(board.population(), 3);
+    /// assert!(board.is_alive(&Coord::new(1, 0)));
+    /// assert!(board.is_alive(&Coord::new(2, 0)));
+    /// assert!(board.is_alive(&Coord::new(3, 0)));
+    /// ```
+    pub fn advance(&mut self, rules: &Rules) {
+        let mut neighbour_counts: HashMap<Coord, u8> = HashMap::new();
+        for coord in &self.live {
+            for (dx, dy) in NEIGHBOUR_OFFSETS {
+                let neighbour = coord.translate_wrapping(dx, dy, self.width, self.height);
+                *neighbour_counts.entry(neighbour).or_insert(0) += 1;
+            }
+        }
+
+        let count = |coord: &Coord| neighbour_counts.get(coord).copied().unwrap_or(0);
+        let survivors = self.live.iter().filter(|c| rules.survives(count(c)));
+
+        let next: HashSet<Coord> = if rules.birthed(0) {
+            let births = (0..self.height)
+                .flat_map(|y| (0..self.width).map(move |x| Coord::new(x, y)))
+                .filter(|c| !self.live.contains(c) && rules.birthed(count(c)));
+
+            survivors.cloned().chain(births).collect()
+        } else {
+            let births = neighbour_counts
+                .iter()
+                .filter(|(c, &n)| !self.live.contains(c) && rules.birthed(n))
+                .map(|(c, _)| c.clone());
+
+            survivors.cloned().chain(births).collect()
+        };
+
+        self.live = next;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::Game;
+    use crate::rules;
+
+    #[test]
+    fn advancing_matches_game_across_edges() {
+        for rules in [rules::conways(), rules::highlife(), rules::day_and_night()] {
+            let board = Board::random(11, 8, 0.4, 3);
+            let mut sparse = SparseBoard::from_board(&board);
+            let mut game = Game::new(board, rules.clone());
+
+            for _ in 0..10 {
+                sparse.advance(&rules);
+                game.advance_to_next_gen();
+
+                assert_eq!(&sparse.to_board(), game.board());
+            }
+        }
+    }
+
+    #[test]
+    fn advancing_with_b0_rules_births_empty_space() {
+        let b0 = Rules {
+            b: vec![0],
+            s: vec![],
+        };
+        let mut sparse = SparseBoard::new(4, 3);
+
+        sparse.advance(&b0);
+
+        assert_eq!(sparse.population(), 12);
+    }
+
+    #[test]
+    fn glider_crosses_huge_board_edge() {
+        let mut board = SparseBoard::new(1_000_000, 1_000_000);
+        for (x, y) in [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)] {
+            board.revive_cell(&Coord::new(x + 999_997, y + 999_997));
+        }
+
+        for _ in 0..12 {
+            board.advance(&rules::conways());
+        }
+
+        assert_eq!(board.population(), 5);
+        assert!(board.live_cells().all(|c| c.x < 3 && c.y < 3));
+    }
+
+    #[test]
+    #[should_panic]
+    fn reviving_cell_outside_board_panics() {
+        SparseBoard::new(3, 3).revive_cell(&Coord::new(3, 0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn converting_board_with_dead_boundary_panics() {
+        let mut board = Board::new(4, 4);
+        board.set_boundary(Boundary::Dead);
+
+        SparseBoard::from_board(&board);
+    }
+
+    #[test]
+    #[should_panic]
+    fn converting_board_with_von_neumann_neighbourhood_panics() {
+        let mut board = Board::new(4, 4);
+        board.set_neighbourhood(Neighbourhood::VonNeumann);
+
+        SparseBoard::from_board(&board);
+    }
+}
@@ -0,0 +1,10 @@
+/// Ways the cells of a pattern can be combined with the cells of the board it is stamped onto
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StampMode {
+    /// Live cells of the pattern are revived, and the rest of the board is left alone
+    Or,
+    /// Cells of the board are overwritten with the cells of the pattern, both live and dead
+    Copy,
+    /// Cells of the board under live cells of the pattern are toggled
+    Xor,
+}
@@ -0,0 +1,12 @@
+/// Kinds of mirror symmetry a board can be given
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymmetryKind {
+    /// Mirrored from left to right, so each row reads the same in both directions
+    Horizontal,
+    /// Mirrored from top to bottom, so each column reads the same in both directions
+    Vertical,
+    /// Mirrored along the diagonal from the top left corner, swapping rows and columns
+    Diagonal,
+    /// Mirrored both from left to right and from top to bottom
+    FourFold,
+}
@@ -0,0 +1,234 @@
+use crate::board::Board;
+use crate::coord::Coord;
+use crate::patterns;
+use crate::rle::MAX_DECODED_CELLS;
+use crate::stamp_mode::StampMode;
+use serde::Deserialize;
+
+/// A description of a board as a list of patterns placed on an otherwise empty board, for use in
+/// configuration files. Templates with a width or height of 0, or with more than 2^24 cells, are
+/// rejected when deserialized
+///
+/// # Examples
+/// ```
+/// use game_of_life::{BoardTemplate, Coord, CellState};
+///
+/// let template: BoardTemplate = serde_json::from_str(r##"{
+///     "width": 10,
+///     "height": 10,
+///     "placements": [
+///         { "pattern": "block", "at": [1, 1] },
+///         { "grid": ["_##"], "at": [5, 5] }
+///     ]
+/// }"##).unwrap();
+///
+/// let board = template.to_board();
+///
+/// assert_eq!(board.get_cell_state(&Coord::new(2, 2)), &CellState::Alive);
+/// assert_eq!(board.get_cell_state(&Coord::new(7, 5)), &CellState::Alive);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "RawBoardTemplate")]
+pub struct BoardTemplate {
+    /// Width of the board
+    pub width: usize,
+    /// Height of the board
+    pub height: usize,
+    /// Patterns to place on the board
+    pub placements: Vec<Placement>,
+}
+
+impl BoardTemplate {
+    /// Creates a board with all the patterns placed on it. Live cells of a pattern are added to the
+    /// board, and cells falling outside the board are left out
+    ///
+    /// # Panics
+    /// If width or height is 0
+    pub fn to_board(&self) -> Board {
+        let mut board = Board::new(self.width, self.height);
+
+        for placement in &self.placements {
+            board.stamp(&placement.pattern, &placement.at, StampMode::Or);
+        }
+
+        board
+    }
+}
+
+/// A board template as it is written in a configuration file, before its size is checked
+#[derive(Deserialize)]
+struct RawBoardTemplate {
+    width: usize,
+    height: usize,
+    placements: Vec<Placement>,
+}
+
+impl TryFrom<RawBoardTemplate> for BoardTemplate {
+    type Error = String;
+
+    fn try_from(raw: RawBoardTemplate) -> Result<BoardTemplate, String> {
+        if raw.width == 0 || raw.height == 0 {
+            return Err("Width and height must be at least 1".to_string());
+        }
+
+        if raw
+            .width
+            .checked_mul(raw.height)
+            .map_or(true, |cells| cells > MAX_DECODED_CELLS)
+        {
+            return Err(format!("Board has more than {} cells", MAX_DECODED_CELLS));
+        }
+
+        Ok(BoardTemplate {
+            width: raw.width,
+            height: raw.height,
+            placements: raw.placements,
+        })
+    }
+}
+
+/// A pattern placed at an offset on a board. The pattern is either given by name, like
+/// `{"pattern": "glider", "at": [5, 5]}`, or inline as rows with `#` as live cells, like
+/// `{"grid": ["_#", "#_"], "at": [0, 0]}`. See [`patterns::by_name`] for the known names
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "RawPlacement")]
+pub struct Placement {
+    /// The pattern to place
+    pub pattern: Board,
+    /// Where to place the top left corner of the pattern
+    pub at: Coord,
+}
+
+/// The different ways a pattern can be given in a template
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawPattern {
+    Named { pattern: String },
+    Grid { grid: Vec<String> },
+}
+
+/// A placement as it is written in a template, before the pattern is looked up or parsed
+#[derive(Deserialize)]
+struct RawPlacement {
+    #[serde(flatten)]
+    pattern: RawPattern,
+    at: (usize, usize),
+}
+
+impl TryFrom<RawPlacement> for Placement {
+    type Error = String;
+
+    fn try_from(raw: RawPlacement) -> Result<Placement, String> {
+        let pattern = match raw.pattern {
+            RawPattern::Named { pattern } => patterns::by_name(&pattern)
+                .ok_or_else(|| format!("Unknown pattern '{}'", pattern))?,
+            RawPattern::Grid { grid } => {
+                if grid.iter().all(String::is_empty) {
+                    return Err("Grid has no cells".to_string());
+                }
+                Board::from_str(&grid.join("\n"), '#')
+            }
+        };
+
+        Ok(Placement {
+            pattern,
+            at: Coord::new(raw.at.0, raw.at.1),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn deserializes_named_patterns_at_offsets() {
+        let template: BoardTemplate = serde_json::from_str(
+            r#"{
+                "width": 12,
+                "height": 8,
+                "placements": [
+                    { "pattern": "glider", "at": [1, 1] },
+                    { "pattern": "block", "at": [8, 4] }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let board = template.to_board();
+
+        let expected = Board::from_str(
+            &[
+                "____________",
+                "__#_________",
+                "___#________",
+                "_###________",
+                "________##__",
+                "________##__",
+                "____________",
+                "____________",
+            ]
+            .join("\n"),
+            '#',
+        );
+        assert_eq!(board, expected);
+    }
+
+    #[test]
+    fn deserializes_inline_grid() {
+        let template: BoardTemplate = serde_json::from_str(
+            r##"{
+                "width": 4,
+                "height": 3,
+                "placements": [{ "grid": ["#_#"], "at": [1, 1] }]
+            }"##,
+        )
+        .unwrap();
+
+        let board = template.to_board();
+
+        assert_eq!(board.to_str('#', '_'), ["____", "_#_#", "____"].join("\n"));
+    }
+
+    #[test]
+    fn clips_patterns_outside_board() {
+        let template: BoardTemplate = serde_json::from_str(
+            r#"{
+                "width": 3,
+                "height": 3,
+                "placements": [{ "pattern": "block", "at": [2, 2] }]
+            }"#,
+        )
+        .unwrap();
+
+        let board = template.to_board();
+
+        assert_eq!(board.to_str('#', '_'), ["___", "___", "__#"].join("\n"));
+    }
+
+    #[test]
+    fn rejects_unknown_pattern_names() {
+        let result: Result<BoardTemplate, _> = serde_json::from_str(
+            r#"{
+                "width": 3,
+                "height": 3,
+                "placements": [{ "pattern": "unicorn", "at": [0, 0] }]
+            }"#,
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn rejects_empty_and_oversized_boards() {
+        for (width, height) in [(0, 3), (3, 0), (1 << 13, 1 << 13), (usize::MAX, 2)] {
+            let json = format!(
+                r#"{{ "width": {}, "height": {}, "placements": [] }}"#,
+                width, height
+            );
+            let result: Result<BoardTemplate, _> = serde_json::from_str(&json);
+
+            assert!(result.is_err(), "{} by {} was accepted", width, height);
+        }
+    }
+}
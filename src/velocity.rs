@@ -0,0 +1,70 @@
+use std::fmt;
+
+/// Velocity of a moving pattern, as a displacement over a number of generations
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Velocity {
+    /// Cells moved along the x axis during one period
+    pub dx: isize,
+    /// Cells moved along the y axis during one period
+    pub dy: isize,
+    /// Number of generations it takes to move the displacement
+    pub period: usize,
+}
+
+impl Velocity {
+    /// Creates a new velocity
+    pub fn new(dx: isize, dy: isize, period: usize) -> Velocity {
+        Velocity { dx, dy, period }
+    }
+
+    /// Checks whether the pattern moves diagonally, like a glider
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::Velocity;
+    ///
+    /// assert!(Velocity::new(1, -1, 4).is_diagonal());
+    /// assert!(!Velocity::new(2, 0, 4).is_diagonal());
+    /// ```
+    pub fn is_diagonal(&self) -> bool {
+        self.dx != 0 && self.dx.abs() == self.dy.abs()
+    }
+
+    /// Checks whether the pattern moves along one of the axes, like a lightweight spaceship
+    ///
+    /// # Examples
+    /// ```
+    /// use game_of_life::Velocity;
+    ///
+    /// assert!(Velocity::new(0, 2, 4).is_orthogonal());
+    /// assert!(!Velocity::new(1, 1, 4).is_orthogonal());
+    /// ```
+    pub fn is_orthogonal(&self) -> bool {
+        (self.dx == 0) != (self.dy == 0)
+    }
+}
+
+impl fmt::Display for Velocity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})c/{}", self.dx, self.dy, self.period)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn displays_displacement_and_period() {
+        assert_eq!(format!("{}", Velocity::new(1, 1, 4)), "(1, 1)c/4");
+        assert_eq!(format!("{}", Velocity::new(-2, 0, 4)), "(-2, 0)c/4");
+    }
+
+    #[test]
+    fn stationary_pattern_is_neither_diagonal_nor_orthogonal() {
+        let velocity = Velocity::new(0, 0, 2);
+
+        assert!(!velocity.is_diagonal());
+        assert!(!velocity.is_orthogonal());
+    }
+}